@@ -5,6 +5,9 @@ use super::types::*;
 use crate::moves;
 use super::zobrist::{ZOBRIST_KEYS, piece_to_index, color_to_index};
 
+/// Casas das torres de roque no xadrez clássico: h1, a1, h8, a8 (ordem dos bits K, Q, k, q)
+pub const STANDARD_CASTLING_ROOKS: [u8; 4] = [7, 0, 63, 56];
+
 // A struct principal do tabuleiro, usando Bitboards.
 #[derive(Debug, Clone, Copy)]
 pub struct Board {
@@ -27,32 +30,72 @@ pub struct Board {
 
     // Direitos de roque (pode_rocar_pequeno_brancas, pode_rocar_grande_brancas, pode_rocar_pequeno_pretas, pode_rocar_grande_pretas)
     pub castling_rights: u8, // Bits: 0=K, 1=Q, 2=k, 3=q
+    // Casa inicial da torre de cada direito de roque, na mesma ordem dos bits (suporta Chess960)
+    pub castling_rooks: [u8; 4],
 
-    // Cache do estado de xeque para otimização
-    pub white_king_in_check: bool,
-    pub black_king_in_check: bool,
+    // Cache do estado de xeque para otimização; ler sempre por `is_king_in_check`
+    pub(crate) white_king_in_check: bool,
+    pub(crate) black_king_in_check: bool,
+    // O flag do lado a jogar ainda não foi calculado (ver `make_move_with_undo`)
+    pub(crate) check_pending: bool,
 
     // Para detecção de draws
     pub halfmove_clock: u16,   // Contador para regra dos 50 movimentos
+    pub fullmove_number: u16,  // Número do lance completo (começa em 1, sobe depois das pretas)
     pub zobrist_hash: u64,     // Hash Zobrist para detecção de repetição
+
+    // Peça em cada casa, mantida em sincronia com os bitboards (consulta O(1))
+    pub mailbox: [Option<Piece>; 64],
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Board {
     /// Cria um novo tabuleiro a partir de uma string FEN.
+    /// Tolerante: aceita FEN com 4 campos (sem os contadores, que ficam 0 e 1) ou com os 6,
+    /// e aceita o sufixo `moves <lances UCI>`, aplicando os lances à posição
     pub fn from_fen(fen: &str) -> Result<Self, String> {
-        // Inicializa magic bitboards na primeira chamada
-        crate::moves::magic_bitboards::init_magic_bitboards();
+        let (fen, moves) = match fen.split_once(" moves") {
+            Some((fen, moves)) => (fen, Some(moves)),
+            None => (fen, None),
+        };
+
+        let parts: Vec<&str> = fen.split_whitespace().collect();
+        if parts.len() != 4 && parts.len() != 6 {
+            return Err("Invalid FEN: Wrong number of parts".to_string());
+        }
+
+        let mut board = Self::parse_fen_fields(&parts)?;
+        if let Some(moves) = moves {
+            board.push_uci_line(moves)?;
+        }
+        Ok(board)
+    }
+
+    /// Cria um tabuleiro a partir de uma FEN com exatamente os seis campos
+    pub fn from_fen_strict(fen: &str) -> Result<Self, String> {
         let parts: Vec<&str> = fen.split_whitespace().collect();
         if parts.len() != 6 {
             return Err("Invalid FEN: Wrong number of parts".to_string());
         }
+        Self::parse_fen_fields(&parts)
+    }
+
+    /// Interpreta os campos de uma FEN (quatro ou seis; os contadores são opcionais)
+    fn parse_fen_fields(parts: &[&str]) -> Result<Self, String> {
 
         let mut board = Board {
             pawns: 0, knights: 0, bishops: 0, rooks: 0, queens: 0, kings: 0,
             white_pieces: 0, black_pieces: 0,
             to_move: Color::White, en_passant_target: None, castling_rights: 0,
-            white_king_in_check: false, black_king_in_check: false,
-            halfmove_clock: 0, zobrist_hash: 0,
+            castling_rooks: STANDARD_CASTLING_ROOKS,
+            white_king_in_check: false, black_king_in_check: false, check_pending: false,
+            halfmove_clock: 0, fullmove_number: 1, zobrist_hash: 0,
+            mailbox: [None; 64],
         };
 
         // Parse board (parts[0])
@@ -61,23 +104,26 @@ impl Board {
             return Err("Invalid FEN: Wrong number of rows".to_string());
         }
 
-        let mut sq = 56; // Start from a8 (rank 8)
-        for row in rows {
+        for (row_index, row) in rows.iter().enumerate() {
+            let row_start = (7 - row_index as u8) * 8; // Start from a8 (rank 8)
+            let mut sq = row_start;
             for ch in row.chars() {
                 if let Some(digit) = ch.to_digit(10) {
                     sq += digit as u8; // Skip empty squares
                 } else {
+                    if sq >= row_start + 8 {
+                        return Err("Invalid FEN: Row too long".to_string());
+                    }
                     let bb = 1u64 << sq;
                     let is_white = ch.is_uppercase();
-                    let piece = ch.to_ascii_lowercase();
-                    match piece {
-                        'p' => board.pawns |= bb,
-                        'n' => board.knights |= bb,
-                        'b' => board.bishops |= bb,
-                        'r' => board.rooks |= bb,
-                        'q' => board.queens |= bb,
-                        'k' => board.kings |= bb,
-                        _ => return Err(format!("Invalid piece: {}", ch)),
+                    match PieceKind::from_char(ch) {
+                        Some(PieceKind::Pawn) => board.pawns |= bb,
+                        Some(PieceKind::Knight) => board.knights |= bb,
+                        Some(PieceKind::Bishop) => board.bishops |= bb,
+                        Some(PieceKind::Rook) => board.rooks |= bb,
+                        Some(PieceKind::Queen) => board.queens |= bb,
+                        Some(PieceKind::King) => board.kings |= bb,
+                        None => return Err(format!("Invalid piece: {}", ch)),
                     }
                     if is_white {
                         board.white_pieces |= bb;
@@ -87,7 +133,6 @@ impl Board {
                     sq += 1;
                 }
             }
-            sq -= 16; // Next rank down
         }
 
         // To move (parts[1])
@@ -97,37 +142,110 @@ impl Board {
             _ => return Err("Invalid turn".to_string()),
         };
 
-        // Castling (parts[2])
+        // Castling (parts[2]): KQkq (torre mais exterior) ou letras de coluna Shredder/X-FEN (Chess960)
         for ch in parts[2].chars() {
-            match ch {
-                'K' => board.castling_rights |= 0b0001,
-                'Q' => board.castling_rights |= 0b0010,
-                'k' => board.castling_rights |= 0b0100,
-                'q' => board.castling_rights |= 0b1000,
-                '-' => {},
-                _ => return Err("Invalid castling".to_string()),
+            if ch == '-' {
+                continue;
             }
+            let color = if ch.is_ascii_uppercase() { Color::White } else { Color::Black };
+            let back_rank = if color == Color::White { 0 } else { 56 };
+            let king_bb = board.pieces_of_kind(color, PieceKind::King) & (0xFFu64 << back_rank);
+            let king_file = if king_bb != 0 { king_bb.trailing_zeros() as u8 % 8 } else { 4 };
+            let rooks = board.pieces_of_kind(color, PieceKind::Rook);
+
+            let (kingside, rook_file) = match ch.to_ascii_lowercase() {
+                'k' => (true, (king_file + 1..8).rev().find(|&f| rooks & (1u64 << (back_rank + f)) != 0).unwrap_or(7)),
+                'q' => (false, (0..king_file).find(|&f| rooks & (1u64 << (back_rank + f)) != 0).unwrap_or(0)),
+                file @ 'a'..='h' => {
+                    let file = file as u8 - b'a';
+                    (file > king_file, file)
+                }
+                _ => return Err("Invalid castling".to_string()),
+            };
+
+            let index = if color == Color::White { 0 } else { 2 } + if kingside { 0 } else { 1 };
+            board.castling_rights |= 1 << index;
+            board.castling_rooks[index] = back_rank + rook_file;
         }
 
         // En passant (parts[3])
         if parts[3] != "-" {
-            let file = (parts[3].as_bytes()[0] - b'a') as u8;
-            let rank = (parts[3].as_bytes()[1] - b'1') as u8;
-            board.en_passant_target = Some(rank * 8 + file);
+            match parts[3].as_bytes() {
+                &[file @ b'a'..=b'h', rank @ (b'3' | b'6')] => {
+                    board.en_passant_target = Some((rank - b'1') * 8 + (file - b'a'));
+                }
+                _ => return Err("Invalid FEN: Invalid en passant square".to_string()),
+            }
         }
 
-        // Halfmove clock (parts[4])
-        board.halfmove_clock = parts[4].parse().unwrap_or(0);
+        // Halfmove clock (parts[4]), 0 se omitido
+        board.halfmove_clock = match parts.get(4) {
+            Some(clock) => clock.parse().map_err(|_| "Invalid FEN: Invalid halfmove clock".to_string())?,
+            None => 0,
+        };
+        // Número do lance (parts[5]), 1 se omitido ou 0
+        board.fullmove_number = match parts.get(5) {
+            Some(number) => number.parse::<u16>().map_err(|_| "Invalid FEN: Invalid fullmove number".to_string())?.max(1),
+            None => 1,
+        };
 
+        board.rebuild_mailbox();
         board.update_check_cache();
         board.zobrist_hash = board.compute_zobrist_hash();
         Ok(board)
     }
 
+    /// Converte o tabuleiro para FEN
+    pub fn to_fen(&self) -> String {
+        let side = if self.to_move == Color::White { "w" } else { "b" };
+        format!(
+            "{} {} {} {} {} {}",
+            self.fen_piece_placement(),
+            side,
+            self.fen_castling(),
+            self.fen_en_passant(),
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+
+    /// Campo de colocação das peças da FEN (p.ex. "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR")
+    pub fn fen_piece_placement(&self) -> String {
+        fen_placement(&self.mailbox)
+    }
+
+    /// Direitos de roque no formato FEN ("KQkq", "-", ...)
+    /// Torres fora dos cantos (Chess960) são escritas com a letra da coluna (Shredder-FEN)
+    fn fen_castling(&self) -> String {
+        let mut castling = String::new();
+        for (index, ch) in ['K', 'Q', 'k', 'q'].into_iter().enumerate() {
+            if (self.castling_rights & (1 << index)) == 0 {
+                continue;
+            }
+            let rook_square = self.castling_rooks[index];
+            if rook_square == STANDARD_CASTLING_ROOKS[index] {
+                castling.push(ch);
+            } else {
+                let file = (b'a' + rook_square % 8) as char;
+                castling.push(if index < 2 { file.to_ascii_uppercase() } else { file });
+            }
+        }
+        if castling.is_empty() {
+            castling.push('-');
+        }
+        castling
+    }
+
+    /// Casa de en passant no formato FEN ("e3" ou "-")
+    fn fen_en_passant(&self) -> String {
+        match self.en_passant_target {
+            Some(sq) => format!("{}{}", (b'a' + sq % 8) as char, (b'1' + sq / 8) as char),
+            None => "-".to_string(),
+        }
+    }
+
     /// Cria um novo tabuleiro na posição inicial padrão usando bitboards.
     pub fn new() -> Self {
-        // Inicializa magic bitboards na primeira chamada
-        crate::moves::magic_bitboards::init_magic_bitboards();
         const WHITE_PAWNS: Bitboard = 0b00000000_00000000_00000000_00000000_00000000_00000000_11111111_00000000;
         const WHITE_ROOKS: Bitboard = 0b00000000_00000000_00000000_00000000_00000000_00000000_00000000_10000001;
         const WHITE_KNIGHTS: Bitboard = 0b00000000_00000000_00000000_00000000_00000000_00000000_00000000_01000010;
@@ -154,34 +272,94 @@ impl Board {
             to_move: Color::White,
             en_passant_target: None,
             castling_rights: 0b1111, // Todos os roques inicialmente permitidos
+            castling_rooks: STANDARD_CASTLING_ROOKS,
             white_king_in_check: false,
             black_king_in_check: false,
+            check_pending: false,
             halfmove_clock: 0,
+            fullmove_number: 1,
             zobrist_hash: 0,
+            mailbox: [None; 64],
         };
 
+        board.rebuild_mailbox();
         board.zobrist_hash = board.compute_zobrist_hash();
         board
     }
 
+    /// Bitboard de todas as casas ocupadas
+    #[inline(always)]
+    pub fn occupied(&self) -> Bitboard {
+        self.white_pieces | self.black_pieces
+    }
+
+    /// Bitboard das peças de uma cor
+    #[inline(always)]
+    pub fn pieces(&self, color: Color) -> Bitboard {
+        if color == Color::White { self.white_pieces } else { self.black_pieces }
+    }
+
+    /// Bitboard das peças de um tipo e cor específicos
+    #[inline(always)]
+    pub fn pieces_of_kind(&self, color: Color, kind: PieceKind) -> Bitboard {
+        let kind_bb = match kind {
+            PieceKind::Pawn => self.pawns,
+            PieceKind::Knight => self.knights,
+            PieceKind::Bishop => self.bishops,
+            PieceKind::Rook => self.rooks,
+            PieceKind::Queen => self.queens,
+            PieceKind::King => self.kings,
+        };
+        self.pieces(color) & kind_bb
+    }
+
     /// Gera todos os lances pseudo-legais para todas as peças do jogador atual (ULTRA-OTIMIZADO)
     pub fn generate_all_moves(&self) -> Vec<Move> {
         // Pre-aloca com capacidade otimizada para posições médias
         let mut moves = Vec::with_capacity(100);
+        self.generate_all_moves_into(&mut moves);
+        moves
+    }
 
-        // OTIMIZAÇÃO CRÍTICA: Eliminados TODOS os Vec::extend!
-        moves::pawn::generate_pawn_moves_into(self, &mut moves);
-        moves::knight::generate_knight_moves_into(self, &mut moves);
-        self.generate_sliding_moves(&mut moves);
-        moves::queen::generate_queen_moves_into(self, &mut moves);
-        moves::king::generate_king_moves_into(self, &mut moves);
+    /// Igual a `generate_all_moves`, mas escreve num buffer do chamador (limpo antes): `Vec<Move>` ou `MoveList`
+    /// Reutilizar o buffer entre nós evita uma alocação por posição
+    pub fn generate_all_moves_into<B: MoveBuffer>(&self, moves: &mut B) {
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::get_profiler().start_timer("generate_all_moves");
 
-        moves
+        moves.clear();
+
+        // OTIMIZAÇÃO CRÍTICA: Eliminados TODOS os Vec::extend!
+        moves::pawn::generate_pawn_moves_into(self, moves);
+        moves::knight::generate_knight_moves_into(self, moves);
+        self.generate_sliding_moves(moves);
+        moves::queen::generate_queen_moves_into(self, moves);
+        moves::king::generate_king_moves_into(self, moves);
     }
 
     /// Executa um lance, atualizando o estado do tabuleiro.
     /// Retorna `true` se o lance é legal (não deixa o próprio rei em xeque).
     pub fn make_move(&mut self, mv: Move) -> bool {
+        let moving_color = self.to_move;
+        self.apply_move(mv);
+
+        // Atualiza cache de xeque
+        self.update_check_cache();
+
+        // Verifica se o movimento é legal (não deixa o próprio rei em xeque)
+        !self.is_king_in_check(moving_color)
+    }
+
+    /// Devolve o tabuleiro depois do lance, sem alterar `self` (API por valor)
+    pub fn with_move(&self, mv: Move) -> Board {
+        let mut board = *self;
+        board.make_move(mv);
+        board
+    }
+
+    /// Aplica o lance sem atualizar o cache de xeque
+    /// Quem chama é responsável por recalcular os flags de xeque que precisar
+    fn apply_move(&mut self, mv: Move) {
         let from_bb = 1u64 << mv.from;
         let to_bb = 1u64 << mv.to;
         let moving_color = self.to_move;
@@ -195,7 +373,7 @@ impl Board {
 
         // Verifica se é captura ou movimento de peão (reset halfmove_clock)
         let is_pawn_move = (self.pawns & from_bb) != 0;
-        let is_capture = (if moving_color == Color::White { self.black_pieces } else { self.white_pieces } & to_bb) != 0;
+        let is_capture = (self.pieces(!moving_color) & to_bb) != 0;
 
         if is_pawn_move || is_capture {
             self.halfmove_clock = 0;
@@ -239,52 +417,23 @@ impl Board {
 
         // Trata roque
         if mv.is_castling {
-            // Move o rei
-            if moving_color == Color::White {
-                self.white_pieces ^= from_bb | to_bb;
-                self.kings ^= from_bb | to_bb;
-
-                // Move a torre correspondente + Zobrist
-                if mv.to == 6 { // Roque pequeno
-                    self.white_pieces ^= 0b10000000 | 0b00100000; // h1 -> f1
-                    self.rooks ^= 0b10000000 | 0b00100000;
-                    // Atualiza Zobrist para torre: remove h1, adiciona f1
-                    let rook_idx = piece_to_index(PieceKind::Rook);
-                    self.zobrist_hash ^= ZOBRIST_KEYS.pieces[0][rook_idx][7]; // Remove h1
-                    self.zobrist_hash ^= ZOBRIST_KEYS.pieces[0][rook_idx][5]; // Adiciona f1
-                } else { // Roque grande
-                    self.white_pieces ^= 0b00000001 | 0b00001000; // a1 -> d1
-                    self.rooks ^= 0b00000001 | 0b00001000;
-                    // Atualiza Zobrist para torre: remove a1, adiciona d1
-                    let rook_idx = piece_to_index(PieceKind::Rook);
-                    self.zobrist_hash ^= ZOBRIST_KEYS.pieces[0][rook_idx][0]; // Remove a1
-                    self.zobrist_hash ^= ZOBRIST_KEYS.pieces[0][rook_idx][3]; // Adiciona d1
-                }
-                // Remove direitos de roque das brancas
-                self.castling_rights &= 0b1100;
-            } else {
-                self.black_pieces ^= from_bb | to_bb;
-                self.kings ^= from_bb | to_bb;
-
-                // Move a torre correspondente + Zobrist
-                if mv.to == 62 { // Roque pequeno
-                    self.black_pieces ^= 0x8000000000000000 | 0x2000000000000000; // h8 -> f8
-                    self.rooks ^= 0x8000000000000000 | 0x2000000000000000;
-                    // Atualiza Zobrist para torre: remove h8, adiciona f8
-                    let rook_idx = piece_to_index(PieceKind::Rook);
-                    self.zobrist_hash ^= ZOBRIST_KEYS.pieces[1][rook_idx][63]; // Remove h8
-                    self.zobrist_hash ^= ZOBRIST_KEYS.pieces[1][rook_idx][61]; // Adiciona f8
-                } else { // Roque grande
-                    self.black_pieces ^= 0x0100000000000000 | 0x0800000000000000; // a8 -> d8
-                    self.rooks ^= 0x0100000000000000 | 0x0800000000000000;
-                    // Atualiza Zobrist para torre: remove a8, adiciona d8
-                    let rook_idx = piece_to_index(PieceKind::Rook);
-                    self.zobrist_hash ^= ZOBRIST_KEYS.pieces[1][rook_idx][56]; // Remove a8
-                    self.zobrist_hash ^= ZOBRIST_KEYS.pieces[1][rook_idx][59]; // Adiciona d8
-                }
-                // Remove direitos de roque das pretas
-                self.castling_rights &= 0b0011;
-            }
+            let (rook_from, rook_to) = self.castling_rook_squares(mv);
+            let rook_from_bb = 1u64 << rook_from;
+            let rook_to_bb = 1u64 << rook_to;
+
+            // Retira rei e torre antes de os recolocar: em Chess960 as casas podem coincidir
+            let our_pieces = if moving_color == Color::White { &mut self.white_pieces } else { &mut self.black_pieces };
+            *our_pieces = (*our_pieces & !(from_bb | rook_from_bb)) | to_bb | rook_to_bb;
+            self.kings = (self.kings & !from_bb) | to_bb;
+            self.rooks = (self.rooks & !rook_from_bb) | rook_to_bb;
+
+            // Atualiza Zobrist para a torre
+            let rook_idx = piece_to_index(PieceKind::Rook);
+            self.zobrist_hash ^= ZOBRIST_KEYS.pieces[color_idx][rook_idx][rook_from as usize];
+            self.zobrist_hash ^= ZOBRIST_KEYS.pieces[color_idx][rook_idx][rook_to as usize];
+
+            // Remove os direitos de roque de quem rocou
+            self.castling_rights &= if moving_color == Color::White { 0b1100 } else { 0b0011 };
         } else if mv.is_en_passant {
             // En passant: remove o peão capturado + atualiza Zobrist
             let captured_pawn_square = if moving_color == Color::White { mv.to - 8 } else { mv.to + 8 };
@@ -307,7 +456,7 @@ impl Board {
             self.pawns ^= from_bb | to_bb;
         } else {
             let move_bb = from_bb | to_bb;
-            let enemy_pieces = if moving_color == Color::White { self.black_pieces } else { self.white_pieces };
+            let enemy_pieces = self.pieces(!moving_color);
             let is_capture = (enemy_pieces & to_bb) != 0;
 
             // Trata capturas normais
@@ -372,30 +521,77 @@ impl Board {
             }
         }
 
-        // Atualiza direitos de roque quando torres se movem
-        if mv.from == 0 || mv.to == 0 { self.castling_rights &= 0b1101; } // a1
-        if mv.from == 7 || mv.to == 7 { self.castling_rights &= 0b1110; } // h1
-        if mv.from == 56 || mv.to == 56 { self.castling_rights &= 0b0111; } // a8
-        if mv.from == 63 || mv.to == 63 { self.castling_rights &= 0b1011; } // h8
+        // Atualiza o mailbox (origens primeiro: em Chess960 as casas de rei e torre podem coincidir)
+        self.mailbox[mv.from as usize] = None;
+        if mv.is_castling {
+            let (rook_from, rook_to) = self.castling_rook_squares(mv);
+            self.mailbox[rook_from as usize] = None;
+            self.mailbox[rook_to as usize] = Some(Piece::new(PieceKind::Rook, moving_color));
+        } else if mv.is_en_passant {
+            let captured_pawn_square = if moving_color == Color::White { mv.to - 8 } else { mv.to + 8 };
+            self.mailbox[captured_pawn_square as usize] = None;
+        }
+        self.mailbox[mv.to as usize] = Some(Piece::new(mv.promotion.unwrap_or(piece_kind), moving_color));
 
-        self.to_move = if moving_color == Color::White { Color::Black } else { Color::White };
+        // Atualiza direitos de roque quando torres se movem ou são capturadas
+        self.castling_rights &= !(self.castling_rights_on(mv.from) | self.castling_rights_on(mv.to));
 
-        // Atualiza cache de xeque
-        self.update_check_cache();
+        self.to_move = !moving_color;
+        if moving_color == Color::Black {
+            self.fullmove_number += 1;
+        }
 
         // Atualiza hash Zobrist - adiciona novo estado
         if let Some(ep_square) = self.en_passant_target {
             self.zobrist_hash ^= ZOBRIST_KEYS.en_passant[(ep_square % 8) as usize];
         }
         self.zobrist_hash ^= ZOBRIST_KEYS.castling[self.castling_rights as usize];
-        
-        // Verifica se o movimento é legal (não deixa o próprio rei em xeque)
-        !self.is_king_in_check(moving_color)
+
+        self.check_zobrist_hash();
+    }
+
+    /// Com a feature `hash-check`, entra em pânico se o hash incremental divergir do recalculado
+    /// Sem a feature não faz nada
+    #[inline(always)]
+    fn check_zobrist_hash(&self) {
+        #[cfg(feature = "hash-check")]
+        assert_eq!(
+            self.zobrist_hash,
+            self.compute_zobrist_hash(),
+            "Zobrist hash drift\n{}",
+            self
+        );
+    }
+
+    /// Casas (origem, destino) da torre num lance de roque
+    /// O rei vai sempre para a coluna g ou c e a torre para f ou d, seja qual for a casa inicial
+    #[inline(always)]
+    fn castling_rook_squares(&self, mv: Move) -> (u8, u8) {
+        let back_rank = mv.to & !7;
+        let kingside = mv.to % 8 == 6;
+        let index = if back_rank == 0 { 0 } else { 2 } + if kingside { 0 } else { 1 };
+        let rook_to = back_rank + if kingside { 5 } else { 3 };
+        (self.castling_rooks[index], rook_to)
+    }
+
+    /// Bits dos direitos de roque cuja torre começa na casa indicada
+    #[inline(always)]
+    fn castling_rights_on(&self, square: u8) -> u8 {
+        let mut rights = 0;
+        for (index, &rook_square) in self.castling_rooks.iter().enumerate() {
+            if rook_square == square {
+                rights |= 1 << index;
+            }
+        }
+        rights
     }
 
     /// Verifica se o rei da cor especificada está em xeque (usa cache)
+    /// Se o flag do lado a jogar ficou pendente, é calculado na hora
     pub fn is_king_in_check(&self, color: Color) -> bool {
-        if color == Color::White {
+        if self.check_pending && color == self.to_move {
+            self.compute_king_in_check(color)
+        } else if color == Color::White {
             self.white_king_in_check
         } else {
             self.black_king_in_check
@@ -406,12 +602,13 @@ impl Board {
     fn update_check_cache(&mut self) {
         self.white_king_in_check = self.compute_king_in_check(Color::White);
         self.black_king_in_check = self.compute_king_in_check(Color::Black);
+        self.check_pending = false;
     }
 
     /// Calcula se o rei da cor especificada está em xeque (sem usar cache)
     fn compute_king_in_check(&self, color: Color) -> bool {
         // Encontra a posição do rei
-        let king_bb = self.kings & if color == Color::White { self.white_pieces } else { self.black_pieces };
+        let king_bb = self.pieces_of_kind(color, PieceKind::King);
         if king_bb == 0 { return false; } // Não há rei (situação anormal)
 
         let king_square = king_bb.trailing_zeros() as u8;
@@ -423,7 +620,7 @@ impl Board {
     /// Verifica se uma casa é atacada por peças da cor especificada
     pub fn is_square_attacked_by(&self, square: u8, attacking_color: Color) -> bool {
         let _square_bb = 1u64 << square;
-        let attacking_pieces = if attacking_color == Color::White { self.white_pieces } else { self.black_pieces };
+        let attacking_pieces = self.pieces(attacking_color);
 
         // Early exit: se não há peças atacantes, não há ataques
         if attacking_pieces == 0 { return false; }
@@ -447,7 +644,7 @@ impl Board {
         }
 
         // Verifica ataques de peças deslizantes usando magic bitboards
-        let all_pieces = self.white_pieces | self.black_pieces;
+        let all_pieces = self.occupied();
         
         // Ataques de rainha (combinação de torre + bispo)
         if (self.queens & attacking_pieces) != 0 {
@@ -470,6 +667,242 @@ impl Board {
         false
     }
 
+    /// Peças inimigas que dão xeque ao rei da cor especificada (dois bits = xeque duplo)
+    pub fn checkers(&self, color: Color) -> Bitboard {
+        let king_bb = self.pieces_of_kind(color, PieceKind::King);
+        if king_bb == 0 { return 0; }
+        let king_square = king_bb.trailing_zeros() as u8;
+
+        self.attackers_to(king_square, !color, self.occupied())
+    }
+
+    /// Verifica se um lance dá xeque sem copiar o tabuleiro nem executar o lance.
+    /// Cobre xeques diretos, descobertos e os casos especiais (roque, en passant e promoção).
+    pub fn gives_check(&self, mv: Move) -> bool {
+        let us = self.to_move;
+        let (our_pieces, their_pieces) = if us == Color::White {
+            (self.white_pieces, self.black_pieces)
+        } else {
+            (self.black_pieces, self.white_pieces)
+        };
+
+        let their_king = self.kings & their_pieces;
+        if their_king == 0 { return false; }
+        let king_square = their_king.trailing_zeros() as u8;
+
+        let moved_kind = match self.get_piece_at(mv.from) {
+            Some(piece) => piece.kind,
+            None => return false,
+        };
+        let landed_kind = mv.promotion.unwrap_or(moved_kind);
+
+        let from_bb = 1u64 << mv.from;
+        let to_bb = 1u64 << mv.to;
+
+        // Ocupação e peças deslizantes depois do lance (a peça movida é tratada no xeque direto)
+        let mut occupied = (self.occupied() & !from_bb) | to_bb;
+        let our_diagonal = (self.bishops | self.queens) & our_pieces & !from_bb;
+        let mut our_orthogonal = (self.rooks | self.queens) & our_pieces & !from_bb;
+
+        if mv.is_en_passant {
+            // O peão capturado sai de uma casa diferente de mv.to
+            let captured_square = if us == Color::White { mv.to - 8 } else { mv.to + 8 };
+            occupied &= !(1u64 << captured_square);
+        }
+
+        if mv.is_castling {
+            // A torre do roque pode dar xeque a partir da sua casa final
+            // (em Chess960 o rei pode ir para a casa inicial da torre, por isso reconstrói a ocupação)
+            let (rook_from, rook_to) = self.castling_rook_squares(mv);
+            occupied = (self.occupied() & !from_bb & !(1u64 << rook_from)) | to_bb | (1u64 << rook_to);
+            our_orthogonal = (our_orthogonal & !(1u64 << rook_from)) | (1u64 << rook_to);
+        }
+
+        // Xeque direto: a peça na casa de destino ataca o rei inimigo
+        let direct_attacks = match landed_kind {
+            PieceKind::Pawn => crate::moves::pawn::get_pawn_attacks(mv.to, us),
+            PieceKind::Knight => crate::moves::knight::get_knight_attacks(mv.to),
+            PieceKind::Bishop => crate::moves::magic_bitboards::get_bishop_attacks_magic(mv.to, occupied),
+            PieceKind::Rook => crate::moves::magic_bitboards::get_rook_attacks_magic(mv.to, occupied),
+            PieceKind::Queen => crate::moves::magic_bitboards::get_queen_attacks_magic(mv.to, occupied),
+            PieceKind::King => 0,
+        };
+        if (direct_attacks & their_king) != 0 { return true; }
+
+        // Xeque descoberto: uma peça deslizante nossa passa a ver o rei
+        let diagonal_rays = crate::moves::magic_bitboards::get_bishop_attacks_magic(king_square, occupied);
+        if (diagonal_rays & our_diagonal) != 0 { return true; }
+
+        let orthogonal_rays = crate::moves::magic_bitboards::get_rook_attacks_magic(king_square, occupied);
+        (orthogonal_rays & our_orthogonal) != 0
+    }
+
+    /// Verifica se um lance legal dá xeque-mate (mate em 1)
+    /// Filtra com `gives_check` antes de executar o lance numa cópia e procurar uma resposta legal
+    pub fn gives_checkmate(&self, mv: Move) -> bool {
+        if !self.gives_check(mv) {
+            return false;
+        }
+        let mut after = *self;
+        after.make_move(mv);
+        !after.has_legal_move()
+    }
+
+    /// Calcula as peças da cor especificada cravadas (cravação absoluta) contra o próprio rei
+    pub fn pinned_pieces(&self, color: Color) -> Bitboard {
+        let (our_pieces, their_pieces) = if color == Color::White {
+            (self.white_pieces, self.black_pieces)
+        } else {
+            (self.black_pieces, self.white_pieces)
+        };
+
+        let our_king = self.kings & our_pieces;
+        if our_king == 0 { return 0; }
+        let king_square = our_king.trailing_zeros() as u8;
+        let occupied = self.occupied();
+
+        // Peças deslizantes inimigas alinhadas com o rei num tabuleiro vazio
+        let rook_snipers = crate::moves::magic_bitboards::get_rook_attacks_magic(king_square, 0)
+            & (self.rooks | self.queens) & their_pieces;
+        let bishop_snipers = crate::moves::magic_bitboards::get_bishop_attacks_magic(king_square, 0)
+            & (self.bishops | self.queens) & their_pieces;
+
+        let mut pinned = 0u64;
+        let mut snipers = rook_snipers | bishop_snipers;
+        while snipers != 0 {
+            let sniper_square = snipers.trailing_zeros() as u8;
+            snipers &= snipers - 1;
+
+            // Cravação: exatamente uma peça entre o rei e o atacante, e essa peça é nossa
+            let blockers = crate::moves::magic_bitboards::squares_between(king_square, sniper_square) & occupied;
+            if blockers != 0 && (blockers & (blockers - 1)) == 0 && (blockers & our_pieces) != 0 {
+                pinned |= blockers;
+            }
+        }
+
+        pinned
+    }
+
+    /// Verifica se a posição é "quieta": o lado a jogar não está em xeque
+    /// e não tem nenhuma captura ou promoção legal disponível
+    pub fn is_quiet(&self) -> bool {
+        if self.is_king_in_check(self.to_move) {
+            return false;
+        }
+
+        !self.generate_all_moves()
+            .into_iter()
+            .any(|mv| self.is_capture_or_promotion(mv) && self.is_legal_move(mv))
+    }
+
+    /// Verifica se o lance captura uma peça (inclui en passant)
+    #[inline(always)]
+    pub fn is_capture(&self, mv: Move) -> bool {
+        mv.is_en_passant || (self.pieces(!self.to_move) & (1u64 << mv.to)) != 0
+    }
+
+    /// Verifica se o lance é uma promoção
+    #[inline(always)]
+    pub fn is_promotion(&self, mv: Move) -> bool {
+        mv.promotion.is_some()
+    }
+
+    /// Verifica se o lance é tático: captura ou promoção
+    #[inline(always)]
+    pub fn is_capture_or_promotion(&self, mv: Move) -> bool {
+        self.is_capture(mv) || self.is_promotion(mv)
+    }
+
+    /// Todas as peças da cor especificada que atacam a casa, com a ocupação dada
+    /// A ocupação é explícita para permitir recalcular raios X (p.ex. no SEE)
+    pub fn attackers_to(&self, square: u8, color: Color, occupied: Bitboard) -> Bitboard {
+        // Um peão da cor ataca a casa se estiver onde um peão adversário na casa atacaria
+        let attackers = (crate::moves::pawn::get_pawn_attacks(square, !color) & self.pawns)
+            | (crate::moves::knight::get_knight_attacks(square) & self.knights)
+            | (crate::moves::king::get_king_attacks(square) & self.kings)
+            | (crate::moves::magic_bitboards::get_bishop_attacks_magic(square, occupied) & (self.bishops | self.queens))
+            | (crate::moves::magic_bitboards::get_rook_attacks_magic(square, occupied) & (self.rooks | self.queens));
+
+        attackers & self.pieces(color)
+    }
+
+    /// Atacante de menor valor da cor especificada sobre a casa, com a ocupação dada
+    /// Peças fora de `occupied` contam como já removidas (p.ex. trocadas numa sequência de SEE)
+    pub fn smallest_attacker(&self, square: u8, color: Color, occupied: Bitboard) -> Option<(u8, PieceKind)> {
+        let attackers = self.attackers_to(square, color, occupied) & occupied;
+        if attackers == 0 {
+            return None;
+        }
+
+        PieceKind::ALL.iter().find_map(|&kind| {
+            let bb = attackers & self.pieces_of_kind(color, kind);
+            (bb != 0).then(|| (bb.trailing_zeros() as u8, kind))
+        })
+    }
+
+    /// SEE por limiar: verifica se a troca iniciada pelo lance ganha pelo menos `threshold` centipeões.
+    /// Termina assim que o resultado fica decidido, sem calcular o valor exato da troca.
+    /// Cravações são ignoradas e a promoção não entra no saldo.
+    pub fn see_ge(&self, mv: Move, threshold: i32) -> bool {
+        // O roque nunca ganha nem perde material
+        if mv.is_castling {
+            return threshold <= 0;
+        }
+
+        let from_bb = 1u64 << mv.from;
+        let to_bb = 1u64 << mv.to;
+        let mut occupied = self.occupied();
+
+        let captured_value = if mv.is_en_passant {
+            let captured_square = if self.to_move == Color::White { mv.to - 8 } else { mv.to + 8 };
+            occupied &= !(1u64 << captured_square);
+            PieceKind::Pawn.value()
+        } else {
+            self.get_piece_at(mv.to).map_or(0, |piece| piece.kind.value())
+        };
+        let moved_value = match self.get_piece_at(mv.from) {
+            Some(piece) => piece.kind.value(),
+            None => return false,
+        };
+
+        // Mesmo perdendo a peça movida de graça, o saldo fica abaixo do limiar?
+        let mut swap = captured_value - threshold;
+        if swap < 0 { return false; }
+
+        // Mesmo que o adversário recapture a peça movida, o saldo continua acima do limiar?
+        swap = moved_value - swap;
+        if swap <= 0 { return true; }
+
+        occupied ^= from_bb | to_bb;
+        let mut side = self.to_move;
+        let mut result = true;
+
+        loop {
+            side = !side;
+
+            // Recaptura sempre com o atacante de menor valor; os raios X aparecem ao tirá-lo da ocupação
+            let (square, kind) = match self.smallest_attacker(mv.to, side, occupied) {
+                Some(attacker) => attacker,
+                None => break,
+            };
+            result = !result;
+
+            if kind == PieceKind::King {
+                // O rei só pode recapturar se o adversário já não tiver atacantes
+                if (self.attackers_to(mv.to, !side, occupied) & occupied) != 0 {
+                    result = !result;
+                }
+                break;
+            }
+
+            swap = kind.value() - swap;
+            if swap < result as i32 { break; }
+
+            occupied ^= 1u64 << square;
+        }
+
+        result
+    }
 
     /// Verifica se a posição atual é xeque-mate
     pub fn is_checkmate(&self) -> bool {
@@ -477,12 +910,7 @@ impl Board {
             return false;
         }
 
-        let moves = self.generate_all_moves();
-        moves.iter().all(|&mv| {
-            let mut temp = *self;
-            temp.make_move(mv);
-            temp.is_king_in_check(self.to_move)
-        })
+        !self.has_legal_move()
     }
 
     /// Verifica se a posição atual é empate por afogamento
@@ -491,17 +919,12 @@ impl Board {
             return false;
         }
 
-        let moves = self.generate_all_moves();
-        moves.iter().all(|&mv| {
-            let mut temp = *self;
-            temp.make_move(mv);
-            temp.is_king_in_check(self.to_move)
-        })
+        !self.has_legal_move()
     }
 
     /// Verifica se há empate por material insuficiente
     pub fn is_draw_by_insufficient_material(&self) -> bool {
-        let total_pieces = self.white_pieces | self.black_pieces;
+        let total_pieces = self.occupied();
         let piece_count = total_pieces.count_ones();
 
         // King vs King
@@ -596,48 +1019,515 @@ impl Board {
         hash
     }
 
+    /// Chave para tabelas de transposição: o hash Zobrist sem a componente de en passant
+    /// quando nenhum peão do lado a jogar pode de facto capturar en passant
+    /// O `zobrist_hash` completo continua a ser o usado para detetar repetições (`is_draw_by_repetition`)
+    pub fn tt_key(&self) -> u64 {
+        match self.en_passant_target {
+            Some(ep_square) if self.capturable_en_passant().is_none() => {
+                self.zobrist_hash ^ ZOBRIST_KEYS.en_passant[(ep_square % 8) as usize]
+            }
+            _ => self.zobrist_hash,
+        }
+    }
+
+    /// Alvo de en passant só se algum peão do lado a jogar puder capturar para lá
+    fn capturable_en_passant(&self) -> Option<u8> {
+        self.en_passant_target.filter(|&ep_square| {
+            (crate::moves::pawn::get_pawn_attacks(ep_square, !self.to_move)
+                & self.pieces_of_kind(self.to_move, PieceKind::Pawn)) != 0
+        })
+    }
+
+    /// Compara duas posições como a regra das repetições as vê: peças, lado a jogar,
+    /// direitos de roque e en passant capturável; ignora o relógio dos 50 lances
+    pub fn same_position(&self, other: &Board) -> bool {
+        self.pawns == other.pawns
+            && self.knights == other.knights
+            && self.bishops == other.bishops
+            && self.rooks == other.rooks
+            && self.queens == other.queens
+            && self.kings == other.kings
+            && self.white_pieces == other.white_pieces
+            && self.black_pieces == other.black_pieces
+            && self.to_move == other.to_move
+            && self.castling_rights == other.castling_rights
+            // Torres de direitos já perdidos não contam
+            && (0..4).all(|index| {
+                (self.castling_rights & (1 << index)) == 0 || self.castling_rooks[index] == other.castling_rooks[index]
+            })
+            && self.capturable_en_passant() == other.capturable_en_passant()
+    }
+
+    /// Calcula o hash Zobrist da posição depois do lance, sem executar o lance
+    /// Aplica os mesmos XORs que `make_move` (útil para prefetch e antecipar repetições)
+    pub fn zobrist_after(&self, mv: Move) -> u64 {
+        let us = self.to_move;
+        let color_idx = color_to_index(us);
+        let mut hash = self.zobrist_hash ^ ZOBRIST_KEYS.side_to_move;
+        if let Some(ep_square) = self.en_passant_target {
+            hash ^= ZOBRIST_KEYS.en_passant[(ep_square % 8) as usize];
+        }
+
+        let moved_kind = match self.get_piece_at(mv.from) {
+            Some(piece) => piece.kind,
+            None => return hash,
+        };
+        let landed_kind = mv.promotion.unwrap_or(moved_kind);
+        hash ^= ZOBRIST_KEYS.pieces[color_idx][piece_to_index(moved_kind)][mv.from as usize];
+        hash ^= ZOBRIST_KEYS.pieces[color_idx][piece_to_index(landed_kind)][mv.to as usize];
+
+        let mut castling_rights = self.castling_rights;
+        let rook_idx = piece_to_index(PieceKind::Rook);
+        if mv.is_castling {
+            let (rook_from, rook_to) = self.castling_rook_squares(mv);
+            hash ^= ZOBRIST_KEYS.pieces[color_idx][rook_idx][rook_from as usize];
+            hash ^= ZOBRIST_KEYS.pieces[color_idx][rook_idx][rook_to as usize];
+        } else if mv.is_en_passant {
+            let captured_square = if us == Color::White { mv.to - 8 } else { mv.to + 8 };
+            hash ^= ZOBRIST_KEYS.pieces[color_to_index(!us)][piece_to_index(PieceKind::Pawn)][captured_square as usize];
+        } else {
+            if self.is_capture(mv) {
+                if let Some(captured) = self.get_piece_at(mv.to) {
+                    hash ^= ZOBRIST_KEYS.pieces[color_to_index(!us)][piece_to_index(captured.kind)][mv.to as usize];
+                }
+            }
+            // Avanço duplo de peão cria um alvo de en passant
+            if moved_kind == PieceKind::Pawn && mv.from.abs_diff(mv.to) == 16 {
+                hash ^= ZOBRIST_KEYS.en_passant[(mv.to % 8) as usize];
+            }
+        }
+
+        // Direitos de roque perdidos ao mover o rei ou mover/capturar uma torre
+        if moved_kind == PieceKind::King {
+            castling_rights &= if us == Color::White { 0b1100 } else { 0b0011 };
+        }
+        castling_rights &= !(self.castling_rights_on(mv.from) | self.castling_rights_on(mv.to));
+        hash ^= ZOBRIST_KEYS.castling[self.castling_rights as usize];
+        hash ^= ZOBRIST_KEYS.castling[castling_rights as usize];
+
+        hash
+    }
+
+    /// Espelha o tabuleiro verticalmente e troca as cores (a1 <-> a8, brancas <-> pretas)
+    /// A posição resultante é equivalente com os papéis invertidos: útil para testar simetria da avaliação
+    pub fn flip(&self) -> Board {
+        let mut flipped = Board {
+            pawns: self.pawns.swap_bytes(),
+            knights: self.knights.swap_bytes(),
+            bishops: self.bishops.swap_bytes(),
+            rooks: self.rooks.swap_bytes(),
+            queens: self.queens.swap_bytes(),
+            kings: self.kings.swap_bytes(),
+            white_pieces: self.black_pieces.swap_bytes(),
+            black_pieces: self.white_pieces.swap_bytes(),
+            to_move: !self.to_move,
+            en_passant_target: self.en_passant_target.map(|sq| sq ^ 56),
+            // KQ <-> kq
+            castling_rights: ((self.castling_rights & 0b0011) << 2) | (self.castling_rights >> 2),
+            castling_rooks: [
+                self.castling_rooks[2] ^ 56,
+                self.castling_rooks[3] ^ 56,
+                self.castling_rooks[0] ^ 56,
+                self.castling_rooks[1] ^ 56,
+            ],
+            white_king_in_check: self.black_king_in_check,
+            black_king_in_check: self.white_king_in_check,
+            check_pending: self.check_pending,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            zobrist_hash: 0,
+            mailbox: [None; 64],
+        };
+        flipped.rebuild_mailbox();
+        flipped.zobrist_hash = flipped.compute_zobrist_hash();
+        flipped
+    }
+
     /// Verifica se o jogo acabou (xeque-mate ou empate)
     pub fn is_game_over(&self) -> bool {
-        self.is_checkmate() || self.is_stalemate() || self.is_draw_by_insufficient_material() || self.is_draw_by_50_moves()
+        self.outcome() != GameResult::Ongoing
+    }
+
+    /// Verifica se a posição é empate: afogamento, regra dos 50 lances ou material insuficiente
+    /// O tabuleiro não guarda o histórico de posições; para incluir a repetição use `is_draw_with_history`
+    pub fn is_draw(&self) -> bool {
+        matches!(self.outcome(), GameResult::Draw(_))
+    }
+
+    /// Como `is_draw`, incluindo a repetição tripla a partir do histórico de hashes (ver `is_draw_by_repetition`)
+    pub fn is_draw_with_history(&self, history: &[u64]) -> bool {
+        matches!(self.outcome_with_history(history), GameResult::Draw(_))
+    }
+
+    /// Verifica a repetição tripla: a posição atual já ocorreu pelo menos duas vezes
+    /// `history` tem o `zobrist_hash` das posições anteriores da partida, da mais antiga à mais recente
+    /// (sem a atual); só contam as posições desde o último lance irreversível com o mesmo lado a jogar
+    pub fn is_draw_by_repetition(&self, history: &[u64]) -> bool {
+        let reversible = (self.halfmove_clock as usize).min(history.len());
+        history[history.len() - reversible..]
+            .iter()
+            .rev()
+            .skip(1)
+            .step_by(2)
+            .filter(|&&hash| hash == self.zobrist_hash)
+            .count()
+            >= 2
+    }
+
+    /// Como `outcome`, devolvendo também `DrawReason::Repetition` a partir do histórico de hashes
+    pub fn outcome_with_history(&self, history: &[u64]) -> GameResult {
+        match self.outcome() {
+            GameResult::Ongoing if self.is_draw_by_repetition(history) => GameResult::Draw(DrawReason::Repetition),
+            result => result,
+        }
+    }
+
+    /// Resultado da partida na posição atual, gerando os lances legais uma única vez
+    pub fn outcome(&self) -> GameResult {
+        if !self.has_legal_move() {
+            if !self.is_king_in_check(self.to_move) {
+                return GameResult::Draw(DrawReason::Stalemate);
+            }
+            return match self.to_move {
+                Color::White => GameResult::BlackWins,
+                Color::Black => GameResult::WhiteWins,
+            };
+        }
+
+        if self.is_draw_by_insufficient_material() {
+            GameResult::Draw(DrawReason::InsufficientMaterial)
+        } else if self.is_draw_by_50_moves() {
+            GameResult::Draw(DrawReason::FiftyMove)
+        } else {
+            GameResult::Ongoing
+        }
+    }
+
+    /// Gera as evasões de xeque pseudo-legais: lances de rei, captura do atacante
+    /// ou interposição no raio do xeque (em xeque duplo, só lances de rei)
+    /// Fora de xeque devolve todos os lances pseudo-legais
+    pub fn generate_evasions(&self) -> Vec<Move> {
+        let checkers = self.checkers(self.to_move);
+        if checkers == 0 {
+            return self.generate_all_moves();
+        }
+
+        if checkers.count_ones() >= 2 {
+            // Xeque duplo: apenas lances de rei podem ser legais
+            let mut king_moves = Vec::with_capacity(8);
+            moves::king::generate_king_moves_into(self, &mut king_moves);
+            return king_moves;
+        }
+
+        let king_bb = self.pieces_of_kind(self.to_move, PieceKind::King);
+        let king_square = king_bb.trailing_zeros() as u8;
+        let checker_square = checkers.trailing_zeros() as u8;
+        let targets = checkers | crate::moves::magic_bitboards::squares_between(king_square, checker_square);
+
+        let mut evasions = self.generate_all_moves();
+        evasions.retain(|mv| {
+            if (king_bb & (1u64 << mv.from)) != 0 || (targets & (1u64 << mv.to)) != 0 {
+                return true;
+            }
+            // En passant evita o xeque se capturar o peão que dá xeque
+            mv.is_en_passant && {
+                let captured_square = if self.to_move == Color::White { mv.to - 8 } else { mv.to + 8 };
+                (checkers & (1u64 << captured_square)) != 0
+            }
+        });
+        evasions
     }
 
     /// Gera apenas movimentos legais (filtra movimentos que deixam o rei em xeque)
     pub fn generate_legal_moves(&self) -> Vec<Move> {
-        let pseudo_legal = self.generate_all_moves();
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::get_profiler().start_timer("generate_legal_moves");
+
+        let pseudo_legal = if self.is_king_in_check(self.to_move) {
+            self.generate_evasions()
+        } else {
+            self.generate_all_moves()
+        };
         pseudo_legal.into_iter()
-            .filter(|&mv| {
-                let mut temp = *self;
-                temp.make_move(mv);
-                !temp.is_king_in_check(self.to_move)
-            })
+            .filter(|&mv| self.is_legal_move(mv))
             .collect()
     }
 
+    /// Verifica se existe pelo menos um lance legal (pára no primeiro encontrado)
+    /// Os pseudo-legais vão para uma `MoveList` na stack e são testados um a um, sem alocar
+    pub fn has_legal_move(&self) -> bool {
+        let mut moves = MoveList::new();
+        self.generate_all_moves_into(&mut moves);
+        moves.iter().any(|&mv| self.is_legal_move(mv))
+    }
+
+    /// Número de lances legais, sem montar a lista de lances legais
+    pub fn legal_moves_count(&self) -> usize {
+        let mut moves = MoveList::new();
+        self.generate_all_moves_into(&mut moves);
+        moves.iter().filter(|&&mv| self.is_legal_move(mv)).count()
+    }
+
     /// Verifica se um movimento é legal
+    /// Só recalcula o xeque do lado que jogou: a cópia é descartada, por isso
+    /// o estado de xeque do adversário nunca é consultado
+    /// Rejeita logo lances mal formados (casas fora do tabuleiro, origem sem peça nossa,
+    /// destino com peça nossa) em vez de os executar; de resto assume um lance pseudo-legal
     pub fn is_legal_move(&self, mv: Move) -> bool {
+        if mv.from >= 64 || mv.to >= 64 {
+            return false;
+        }
+        if !self.get_piece_at(mv.from).is_some_and(|piece| piece.color == self.to_move) {
+            return false;
+        }
+        // No roque Chess960 o rei pode acabar na casa da própria torre (ou ficar onde está)
+        if !mv.is_castling && (self.pieces(self.to_move) & (1u64 << mv.to)) != 0 {
+            return false;
+        }
+
         let mut temp = *self;
-        temp.make_move(mv);
-        !temp.is_king_in_check(self.to_move)
+        temp.apply_move(mv);
+        !temp.compute_king_in_check(self.to_move)
+    }
+
+    /// Procura o lance legal de `from` para `to` (para validar lances arrastados numa interface)
+    /// Deteta roque, en passant e promoção sozinho: o roque aceita o rei na casa final ou sobre a torre,
+    /// e sem `promotion` uma promoção escolhe a dama
+    pub fn find_legal_move(&self, from: u8, to: u8, promotion: Option<PieceKind>) -> Option<Move> {
+        let promotion = promotion.unwrap_or(PieceKind::Queen);
+        self.generate_legal_moves().into_iter().find(|mv| {
+            mv.from == from
+                && (mv.to == to || (mv.is_castling && self.castling_rook_squares(*mv).0 == to))
+                && mv.promotion.is_none_or(|kind| kind == promotion)
+        })
+    }
+
+    /// Converte um lance em notação UCI (p.ex. "e2e4", "e7e8q") no lance legal correspondente
+    /// Os flags de roque e en passant são obtidos a partir dos lances legais da posição
+    pub fn parse_uci_move(&self, uci: &str) -> Result<Move, String> {
+        let bytes = uci.as_bytes();
+        if bytes.len() != 4 && bytes.len() != 5 {
+            return Err(format!("Invalid UCI move: {}", uci));
+        }
+
+        let parse_square = |file: u8, rank: u8| -> Result<u8, String> {
+            if !(b'a'..=b'h').contains(&file) || !(b'1'..=b'8').contains(&rank) {
+                return Err(format!("Invalid UCI move: {}", uci));
+            }
+            Ok((rank - b'1') * 8 + (file - b'a'))
+        };
+        let from = parse_square(bytes[0], bytes[1])?;
+        let to = parse_square(bytes[2], bytes[3])?;
+        let promotion = match bytes.get(4) {
+            None => None,
+            Some(b'n') => Some(PieceKind::Knight),
+            Some(b'b') => Some(PieceKind::Bishop),
+            Some(b'r') => Some(PieceKind::Rook),
+            Some(b'q') => Some(PieceKind::Queen),
+            Some(_) => return Err(format!("Invalid UCI move: {}", uci)),
+        };
+
+        // Em Chess960 o roque escreve-se como "rei captura a própria torre" (p.ex. "b1a1"),
+        // porque o destino do rei pode coincidir com um lance normal do rei (p.ex. "b1c1")
+        let legal_moves = self.generate_legal_moves();
+        let castling = legal_moves.iter().find(|mv| {
+            mv.is_castling && mv.from == from && self.castling_rook_squares(**mv).0 == to
+        });
+        let exact = |want_castling: bool| {
+            legal_moves.iter().find(|mv| {
+                mv.is_castling == want_castling && mv.from == from && mv.to == to && mv.promotion == promotion
+            })
+        };
+        castling
+            .or_else(|| exact(false))
+            .or_else(|| exact(true))
+            .copied()
+            .ok_or_else(|| format!("Illegal move: {}", uci))
+    }
+
+    /// Escreve um lance em notação UCI
+    /// O roque clássico usa o destino do rei ("e1g1"); com o rei ou a torre fora das casas
+    /// clássicas (Chess960) escreve-se o rei a capturar a torre ("b1a1"), como o UCI_Chess960
+    pub fn move_to_uci(&self, mv: Move) -> String {
+        if !mv.is_castling {
+            return mv.to_string();
+        }
+        let (rook_from, _) = self.castling_rook_squares(mv);
+        if mv.from % 8 == 4 && STANDARD_CASTLING_ROOKS.contains(&rook_from) {
+            mv.to_string()
+        } else {
+            format!("{}{}", to_algebraic(mv.from), to_algebraic(rook_from))
+        }
+    }
+
+    /// Aplica uma sequência de lances UCI separados por espaços (p.ex. "e2e4 e7e5 g1f3")
+    /// Pára no primeiro lance inválido ou ilegal, deixando aplicados os anteriores
+    pub fn push_uci_line(&mut self, moves: &str) -> Result<(), String> {
+        for token in moves.split_whitespace() {
+            let mv = self.parse_uci_move(token)?;
+            self.make_move(mv);
+        }
+        Ok(())
+    }
+
+    /// Converte um lance em notação SAN (p.ex. "Nf3", "exd5", "Nbd7", "e8=Q+", "O-O") no lance legal correspondente
+    pub fn parse_san(&self, san: &str) -> Result<Move, String> {
+        let text = san.trim_end_matches(['+', '#', '!', '?']);
+        let legal_moves = self.generate_legal_moves();
+
+        // Roque (também aceita zeros, como em alguns ficheiros PGN)
+        let castling_side = match text {
+            "O-O" | "0-0" => Some(true),
+            "O-O-O" | "0-0-0" => Some(false),
+            _ => None,
+        };
+        if let Some(kingside) = castling_side {
+            return legal_moves
+                .into_iter()
+                .find(|mv| mv.is_castling && (mv.to % 8 == 6) == kingside)
+                .ok_or_else(|| format!("Illegal move: {}", san));
+        }
+
+        // Promoção: "e8=Q" ou "e8Q"
+        let (text, promotion) = match text.char_indices().last() {
+            Some((index, ch)) if index >= 2 && "NBRQ".contains(ch) => {
+                (text[..index].trim_end_matches('='), PieceKind::from_char(ch))
+            }
+            _ => (text, None),
+        };
+
+        let mut chars: Vec<char> = text.chars().filter(|&ch| ch != 'x').collect();
+        let kind = match chars.first() {
+            Some(&ch) if "NBRQK".contains(ch) => {
+                chars.remove(0);
+                PieceKind::from_char(ch).unwrap()
+            }
+            _ => PieceKind::Pawn,
+        };
+        if chars.len() < 2 || chars.len() > 4 {
+            return Err(format!("Invalid SAN move: {}", san));
+        }
+
+        let (file, rank) = (chars[chars.len() - 2], chars[chars.len() - 1]);
+        if !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+            return Err(format!("Invalid SAN move: {}", san));
+        }
+        let to = (rank as u8 - b'1') * 8 + (file as u8 - b'a');
+
+        // Desambiguação opcional por coluna e/ou linha de origem
+        let mut from_file = None;
+        let mut from_rank = None;
+        for &ch in &chars[..chars.len() - 2] {
+            match ch {
+                'a'..='h' => from_file = Some(ch as u8 - b'a'),
+                '1'..='8' => from_rank = Some(ch as u8 - b'1'),
+                _ => return Err(format!("Invalid SAN move: {}", san)),
+            }
+        }
+
+        let mut candidates = legal_moves.into_iter().filter(|mv| {
+            !mv.is_castling
+                && mv.to == to
+                && mv.promotion == promotion
+                && self.get_piece_at(mv.from).map(|piece| piece.kind) == Some(kind)
+                && from_file.is_none_or(|f| mv.from % 8 == f)
+                && from_rank.is_none_or(|r| mv.from / 8 == r)
+        });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(mv), None) => Ok(mv),
+            (Some(_), Some(_)) => Err(format!("Ambiguous move: {}", san)),
+            (None, _) => Err(format!("Illegal move: {}", san)),
+        }
+    }
+
+    /// Aplica uma linha em SAN (p.ex. "1. e4 e5 2. Nf3 Nc6 3. Bb5") e devolve os lances resolvidos
+    /// Ignora números de lance e o resultado final; pára no primeiro lance inválido ou ilegal
+    pub fn apply_moves_san(&mut self, line: &str) -> Result<Vec<Move>, String> {
+        let mut applied = Vec::new();
+        for token in line.split_whitespace() {
+            if matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*") {
+                continue;
+            }
+            // "1." / "1..." / "1.e4": fica só o que vem depois do último ponto
+            let san = token.rsplit('.').next().unwrap_or("");
+            if san.is_empty() {
+                continue;
+            }
+
+            let mv = self.parse_san(san)?;
+            self.make_move(mv);
+            applied.push(mv);
+        }
+        Ok(applied)
     }
 
     /// Retorna o número de peças de cada tipo para avaliação
     pub fn piece_count(&self, color: Color, piece_kind: PieceKind) -> u32 {
-        let color_pieces = if color == Color::White { self.white_pieces } else { self.black_pieces };
-        let piece_bb = match piece_kind {
-            PieceKind::Pawn => self.pawns,
-            PieceKind::Knight => self.knights,
-            PieceKind::Bishop => self.bishops,
-            PieceKind::Rook => self.rooks,
-            PieceKind::Queen => self.queens,
-            PieceKind::King => self.kings,
-        };
-        (color_pieces & piece_bb).count_ones()
+        self.pieces_of_kind(color, piece_kind).count_ones()
+    }
+
+    /// Balanço material em centipeões (brancas - pretas), sem contar os reis
+    pub fn material_balance(&self) -> i32 {
+        PieceKind::ALL[..5]
+            .iter()
+            .map(|&kind| {
+                let diff = self.piece_count(Color::White, kind) as i32 - self.piece_count(Color::Black, kind) as i32;
+                diff * kind.value()
+            })
+            .sum()
+    }
+
+    /// Fase de jogo entre 0 (só reis e peões) e `MAX_PHASE` (material inicial), pelo peso das peças
+    pub fn game_phase(&self) -> i32 {
+        let phase: i32 = PieceKind::ALL
+            .iter()
+            .map(|&kind| kind.phase_weight() * (self.piece_count(Color::White, kind) + self.piece_count(Color::Black, kind)) as i32)
+            .sum();
+        phase.min(MAX_PHASE)
+    }
+
+    /// Balanço material (brancas - pretas) com os valores interpolados pela fase de jogo
+    pub fn tapered_material_balance(&self) -> i32 {
+        let phase = self.game_phase();
+        PieceKind::ALL[..5]
+            .iter()
+            .map(|&kind| {
+                let diff = self.piece_count(Color::White, kind) as i32 - self.piece_count(Color::Black, kind) as i32;
+                diff * kind.tapered_value(phase)
+            })
+            .sum()
+    }
+
+    /// Chave de material: número de peças de cada cor/tipo empacotado em 4 bits por contagem
+    /// Duas posições com o mesmo material têm sempre a mesma chave (sem colisões)
+    pub fn material_key(&self) -> u64 {
+        let mut white = [0u32; 6];
+        let mut black = [0u32; 6];
+        for (i, &kind) in PieceKind::ALL.iter().enumerate() {
+            white[i] = self.piece_count(Color::White, kind);
+            black[i] = self.piece_count(Color::Black, kind);
+        }
+        Self::material_key_for(white, black)
+    }
+
+    /// Calcula a chave de material a partir das contagens [P, N, B, R, Q, K] de cada cor
+    pub const fn material_key_for(white: [u32; 6], black: [u32; 6]) -> u64 {
+        let mut key = 0u64;
+        let mut i = 0;
+        while i < 6 {
+            key |= ((white[i] & 0xF) as u64) << (4 * i);
+            key |= ((black[i] & 0xF) as u64) << (4 * (i + 6));
+            i += 1;
+        }
+        key
     }
 
     /// Verifica se há peões passados (útil para avaliação)
     pub fn has_passed_pawn(&self, color: Color) -> bool {
-        let my_pawns = if color == Color::White { self.white_pieces } else { self.black_pieces } & self.pawns;
-        let enemy_pawns = if color == Color::White { self.black_pieces } else { self.white_pieces } & self.pawns;
+        let my_pawns = self.pieces_of_kind(color, PieceKind::Pawn);
+        let enemy_pawns = self.pieces_of_kind(!color, PieceKind::Pawn);
 
         let mut bb = my_pawns;
         while bb != 0 {
@@ -648,7 +1538,7 @@ impl Board {
             let rank = square / 8;
 
             let front_span = if color == Color::White {
-                let mask = !((1u64 << (rank + 1) * 8) - 1);
+                let mask = !((1u64 << ((rank + 1) * 8)) - 1);
                 mask & (0x0101010101010101u64 << file)
             } else {
                 let mask = (1u64 << (rank * 8)) - 1;
@@ -664,6 +1554,7 @@ impl Board {
     }
 
     /// Executa um movimento e retorna informação para desfazê-lo
+    /// Só calcula o xeque do lado que jogou (o que interessa para a legalidade)
     pub fn make_move_with_undo(&mut self, mv: Move) -> UndoInfo {
         let (captured_piece, captured_square) = self.get_captured_piece(mv);
         let moved_piece = self.get_piece_at(mv.from).unwrap().kind; // Identifica peça movida
@@ -678,12 +1569,63 @@ impl Board {
             old_zobrist_hash: self.zobrist_hash,
             old_white_king_in_check: self.white_king_in_check,
             old_black_king_in_check: self.black_king_in_check,
+            old_check_pending: self.check_pending,
         };
 
-        self.make_move(mv);
+        // Só o xeque do lado que jogou decide a legalidade; o do adversário fica pendente
+        // e é calculado em `is_king_in_check` apenas se for consultado
+        let moving_color = self.to_move;
+        self.apply_move(mv);
+        let mover_in_check = self.compute_king_in_check(moving_color);
+        if moving_color == Color::White {
+            self.white_king_in_check = mover_in_check;
+        } else {
+            self.black_king_in_check = mover_in_check;
+        }
+        self.check_pending = true;
         undo_info
     }
 
+    /// Executa um lance nulo (passa a vez) para a poda de lance nulo
+    /// Limpa o alvo de en passant e mantém o hash Zobrist coerente
+    /// Não deve ser chamado com o lado a jogar em xeque
+    pub fn make_null_move(&mut self) -> NullUndo {
+        // O lado a jogar passa a ser o que jogou: resolve primeiro o flag pendente
+        if self.check_pending {
+            self.update_check_cache();
+        }
+        let undo = NullUndo {
+            old_en_passant_target: self.en_passant_target,
+            old_halfmove_clock: self.halfmove_clock,
+            old_zobrist_hash: self.zobrist_hash,
+        };
+
+        if let Some(ep_square) = self.en_passant_target.take() {
+            self.zobrist_hash ^= ZOBRIST_KEYS.en_passant[(ep_square % 8) as usize];
+        }
+        self.zobrist_hash ^= ZOBRIST_KEYS.side_to_move;
+        self.halfmove_clock += 1;
+        if self.to_move == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.to_move = !self.to_move;
+
+        self.check_zobrist_hash();
+        undo
+    }
+
+    /// Desfaz um lance nulo feito com `make_null_move`
+    pub fn unmake_null_move(&mut self, undo: NullUndo) {
+        self.to_move = !self.to_move;
+        if self.to_move == Color::Black {
+            self.fullmove_number -= 1;
+        }
+        self.en_passant_target = undo.old_en_passant_target;
+        self.halfmove_clock = undo.old_halfmove_clock;
+        self.zobrist_hash = undo.old_zobrist_hash;
+        self.check_zobrist_hash();
+    }
+
     /// Desfaz um movimento usando a informação de UndoInfo (CORRIGIDO PARA MOVIMENTOS ESPECIAIS)
     pub fn unmake_move(&mut self, mv: Move, undo_info: UndoInfo) {
         // Restaura o estado anterior
@@ -693,10 +1635,14 @@ impl Board {
         self.zobrist_hash = undo_info.old_zobrist_hash;
         self.white_king_in_check = undo_info.old_white_king_in_check;
         self.black_king_in_check = undo_info.old_black_king_in_check;
+        self.check_pending = undo_info.old_check_pending;
 
         // Inverte a cor do jogador
         self.to_move = !self.to_move;
         let moving_color = self.to_move; // Cor original da peça que se moveu
+        if moving_color == Color::Black {
+            self.fullmove_number -= 1;
+        }
         let from_bb = 1u64 << mv.from;
         let to_bb = 1u64 << mv.to;
 
@@ -704,40 +1650,21 @@ impl Board {
         // TRATAMENTO ESPECIAL PARA ROQUE
         // ========================================================================
         if mv.is_castling {
-            // Move o rei de volta
-            if moving_color == Color::White {
-                self.white_pieces ^= from_bb | to_bb;
-            } else {
-                self.black_pieces ^= from_bb | to_bb;
-            }
-            self.kings ^= from_bb | to_bb;
-            
-            // Move a torre de volta
-            if moving_color == Color::White {
-                if mv.to == 6 { // Roque pequeno
-                    let rook_from = 1u64 << 5; // f1
-                    let rook_to = 1u64 << 7;   // h1
-                    self.white_pieces ^= rook_from | rook_to;
-                    self.rooks ^= rook_from | rook_to;
-                } else { // Roque grande
-                    let rook_from = 1u64 << 3; // d1
-                    let rook_to = 1u64 << 0;   // a1
-                    self.white_pieces ^= rook_from | rook_to;
-                    self.rooks ^= rook_from | rook_to;
-                }
-            } else {
-                if mv.to == 62 { // Roque pequeno
-                    let rook_from = 1u64 << 61; // f8
-                    let rook_to = 1u64 << 63;   // h8
-                    self.black_pieces ^= rook_from | rook_to;
-                    self.rooks ^= rook_from | rook_to;
-                } else { // Roque grande
-                    let rook_from = 1u64 << 59; // d8
-                    let rook_to = 1u64 << 56;   // a8
-                    self.black_pieces ^= rook_from | rook_to;
-                    self.rooks ^= rook_from | rook_to;
-                }
-            }
+            let (rook_from, rook_to) = self.castling_rook_squares(mv);
+            let rook_from_bb = 1u64 << rook_from;
+            let rook_to_bb = 1u64 << rook_to;
+
+            // Move rei e torre de volta (retira primeiro: as casas podem coincidir em Chess960)
+            let our_pieces = if moving_color == Color::White { &mut self.white_pieces } else { &mut self.black_pieces };
+            *our_pieces = (*our_pieces & !(to_bb | rook_to_bb)) | from_bb | rook_from_bb;
+            self.kings = (self.kings & !to_bb) | from_bb;
+            self.rooks = (self.rooks & !rook_to_bb) | rook_from_bb;
+
+            self.mailbox[mv.to as usize] = None;
+            self.mailbox[rook_to as usize] = None;
+            self.mailbox[rook_from as usize] = Some(Piece::new(PieceKind::Rook, moving_color));
+            self.mailbox[mv.from as usize] = Some(Piece::new(PieceKind::King, moving_color));
+            self.check_zobrist_hash();
             return; // Roque não tem capturas
         }
 
@@ -786,11 +1713,15 @@ impl Board {
             }
         }
 
+        self.mailbox[mv.to as usize] = None;
+        self.mailbox[mv.from as usize] = Some(Piece::new(undo_info.moved_piece, moving_color));
+
         // ========================================================================
         // RESTAURAR PEÇA CAPTURADA (se houver)
         // ========================================================================
         if let Some(captured_piece) = undo_info.captured_piece {
             let captured_bb = 1u64 << undo_info.captured_square;
+            self.mailbox[undo_info.captured_square as usize] = Some(Piece::new(captured_piece, !moving_color));
             
             // Restaura o tipo da peça capturada
             match captured_piece {
@@ -809,10 +1740,25 @@ impl Board {
                 self.white_pieces |= captured_bb;
             }
         }
+
+        self.check_zobrist_hash();
     }
 
-    /// Identifica que peça está em uma casa específica (otimizado para make/unmake)
+    /// Identifica que peça está em uma casa específica (consulta direta ao mailbox)
+    #[inline(always)]
     fn get_piece_at(&self, square: u8) -> Option<Piece> {
+        self.mailbox[square as usize]
+    }
+
+    /// Reconstrói o mailbox a partir dos bitboards (usado ao montar um tabuleiro novo)
+    fn rebuild_mailbox(&mut self) {
+        for square in 0..64u8 {
+            self.mailbox[square as usize] = self.piece_from_bitboards(square);
+        }
+    }
+
+    /// Identifica a peça de uma casa consultando os bitboards um a um
+    fn piece_from_bitboards(&self, square: u8) -> Option<Piece> {
         let bb = 1u64 << square;
         let color = if (self.white_pieces & bb) != 0 {
             Color::White
@@ -853,39 +1799,17 @@ impl Board {
             return (Some(PieceKind::Pawn), captured_square);
         }
         
-        let to_bb = 1u64 << mv.to;
-        let enemy_pieces = if self.to_move == Color::White { 
-            self.black_pieces 
-        } else { 
-            self.white_pieces 
-        };
-        
-        if (enemy_pieces & to_bb) == 0 {
-            return (None, mv.to); // Não há captura
-        }
-        
-        if (self.pawns & to_bb) != 0 {
-            (Some(PieceKind::Pawn), mv.to)
-        } else if (self.knights & to_bb) != 0 {
-            (Some(PieceKind::Knight), mv.to)
-        } else if (self.bishops & to_bb) != 0 {
-            (Some(PieceKind::Bishop), mv.to)
-        } else if (self.rooks & to_bb) != 0 {
-            (Some(PieceKind::Rook), mv.to)
-        } else if (self.queens & to_bb) != 0 {
-            (Some(PieceKind::Queen), mv.to)
-        } else if (self.kings & to_bb) != 0 {
-            (Some(PieceKind::King), mv.to)
-        } else {
-            (None, mv.to)
+        match self.get_piece_at(mv.to) {
+            Some(piece) if piece.color != self.to_move => (Some(piece.kind), mv.to),
+            _ => (None, mv.to), // Não há captura
         }
     }
 
     /// Gera movimentos de peças deslizantes usando magic bitboards diretamente (OTIMIZADO)
     #[inline(always)]
-    fn generate_sliding_moves(&self, moves: &mut Vec<Move>) {
-        let our_pieces = if self.to_move == Color::White { self.white_pieces } else { self.black_pieces };
-        let all_pieces = self.white_pieces | self.black_pieces;
+    fn generate_sliding_moves<B: MoveBuffer>(&self, moves: &mut B) {
+        let our_pieces = self.pieces(self.to_move);
+        let all_pieces = self.occupied();
         
         // Gerar movimentos de bispos
         let mut our_bishops = self.bishops & our_pieces;
@@ -931,4 +1855,26 @@ impl Board {
             our_rooks &= our_rooks - 1;
         }
     }
-}
\ No newline at end of file
+}
+impl std::fmt::Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for rank in (0..8u8).rev() {
+            write!(f, "{} ", rank + 1)?;
+            for file in 0..8u8 {
+                let ch = match self.get_piece_at(rank * 8 + file) {
+                    Some(piece) => fen_char(piece.kind, piece.color),
+                    None => '.',
+                };
+                write!(f, "{}", ch)?;
+                if file < 7 {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "  a b c d e f g h")?;
+
+        let side = if self.to_move == Color::White { "Brancas" } else { "Pretas" };
+        write!(f, "Vez: {} | Roque: {} | En passant: {}", side, self.fen_castling(), self.fen_en_passant())
+    }
+}
@@ -0,0 +1,102 @@
+// Ficheiro: src/core/builder.rs
+// Descrição: Construtor de posições peça a peça, validado pelo mesmo caminho que a FEN.
+
+use super::board::Board;
+use super::types::*;
+
+/// Monta uma posição programaticamente, partindo de um tabuleiro vazio
+/// `build` gera a FEN correspondente e passa-a por `Board::from_fen_strict`,
+/// por isso aplica exatamente as mesmas validações que uma FEN escrita à mão
+#[derive(Debug, Clone)]
+pub struct BoardBuilder {
+    squares: [Option<Piece>; 64],
+    to_move: Color,
+    castling_rights: u8,
+    en_passant: Option<u8>,
+    halfmove_clock: u16,
+    fullmove_number: u16,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardBuilder {
+    /// Tabuleiro vazio, brancas a jogar, sem roques nem en passant
+    pub fn new() -> Self {
+        BoardBuilder {
+            squares: [None; 64],
+            to_move: Color::White,
+            castling_rights: 0,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    /// Coloca uma peça numa casa (substitui a que lá estiver)
+    pub fn put(mut self, square: u8, color: Color, kind: PieceKind) -> Self {
+        self.squares[square as usize] = Some(Piece::new(kind, color));
+        self
+    }
+
+    pub fn side_to_move(mut self, color: Color) -> Self {
+        self.to_move = color;
+        self
+    }
+
+    /// Direitos de roque com os mesmos bits de `Board::castling_rights` (0=K, 1=Q, 2=k, 3=q)
+    pub fn castling(mut self, rights: u8) -> Self {
+        self.castling_rights = rights;
+        self
+    }
+
+    /// Casa alvo de en passant (a casa saltada pelo peão, p.ex. e3)
+    pub fn en_passant(mut self, square: u8) -> Self {
+        self.en_passant = Some(square);
+        self
+    }
+
+    pub fn halfmove_clock(mut self, clock: u16) -> Self {
+        self.halfmove_clock = clock;
+        self
+    }
+
+    pub fn fullmove_number(mut self, number: u16) -> Self {
+        self.fullmove_number = number;
+        self
+    }
+
+    /// FEN da posição montada
+    pub fn fen(&self) -> String {
+        let side = if self.to_move == Color::White { "w" } else { "b" };
+        let castling: String = ['K', 'Q', 'k', 'q']
+            .iter()
+            .enumerate()
+            .filter(|&(bit, _)| self.castling_rights & (1 << bit) != 0)
+            .map(|(_, &letter)| letter)
+            .collect();
+        let castling = if castling.is_empty() { "-".to_string() } else { castling };
+        let en_passant = self.en_passant.map_or("-".to_string(), to_algebraic);
+
+        format!(
+            "{} {} {} {} {} {}",
+            fen_placement(&self.squares),
+            side,
+            castling,
+            en_passant,
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+
+    /// Cria o tabuleiro, com as mesmas validações de `Board::from_fen_strict`
+    pub fn build(&self) -> Result<Board, String> {
+        if self.en_passant.is_some_and(|square| square >= 64) {
+            return Err("Invalid en passant square".to_string());
+        }
+        Board::from_fen_strict(&self.fen())
+    }
+}
@@ -1,7 +1,9 @@
 pub mod board;
+pub mod builder;
 pub mod types;
 pub mod zobrist;
 
 pub use board::*;
+pub use builder::*;
 pub use types::*;
 pub use zobrist::*;
\ No newline at end of file
@@ -12,9 +12,21 @@ pub enum Color {
     Black,
 }
 
+impl Color {
+    /// As duas cores, na ordem usada pelos índices Zobrist (brancas = 0, pretas = 1)
+    pub const BOTH: [Color; 2] = [Color::White, Color::Black];
+
+    /// Cor adversária; equivalente a `!self`
+    #[inline(always)]
+    pub fn opponent(self) -> Color {
+        !self
+    }
+}
+
 impl std::ops::Not for Color {
     type Output = Color;
 
+    #[inline(always)]
     fn not(self) -> Self::Output {
         match self {
             Color::White => Color::Black,
@@ -35,7 +47,51 @@ pub enum PieceKind {
 }
 
 impl PieceKind {
+    /// Todos os tipos de peça, na ordem usada pelos índices Zobrist (peão = 0 ... rei = 5)
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::Pawn,
+        PieceKind::Knight,
+        PieceKind::Bishop,
+        PieceKind::Rook,
+        PieceKind::Queen,
+        PieceKind::King,
+    ];
+
+    /// Converte uma letra FEN (maiúscula ou minúscula) no tipo de peça
+    pub fn from_char(c: char) -> Option<PieceKind> {
+        match c.to_ascii_lowercase() {
+            'p' => Some(PieceKind::Pawn),
+            'n' => Some(PieceKind::Knight),
+            'b' => Some(PieceKind::Bishop),
+            'r' => Some(PieceKind::Rook),
+            'q' => Some(PieceKind::Queen),
+            'k' => Some(PieceKind::King),
+            _ => None,
+        }
+    }
+
+    /// Letra minúscula da peça, como em FEN e nas promoções UCI
+    pub fn to_char(self) -> char {
+        match self {
+            PieceKind::Pawn => 'p',
+            PieceKind::Knight => 'n',
+            PieceKind::Bishop => 'b',
+            PieceKind::Rook => 'r',
+            PieceKind::Queen => 'q',
+            PieceKind::King => 'k',
+        }
+    }
+
+    /// Valor material da peça em centipeões (1 peão = 100).
+    /// É a fonte única dos valores usados em MVV-LVA e no balanço material.
+    /// Alias de `value_mg`, mantido por compatibilidade.
+    #[inline(always)]
     pub fn value(&self) -> i32 {
+        self.value_mg()
+    }
+
+    /// Valor da peça no meio-jogo, em centipeões
+    pub fn value_mg(&self) -> i32 {
         match self {
             PieceKind::Pawn   => 100,
             PieceKind::Knight => 320,
@@ -45,6 +101,97 @@ impl PieceKind {
             PieceKind::King   => 20000, // Valor alto para evitar trocas
         }
     }
+
+    /// Valor da peça no final, em centipeões
+    /// Peões e torres valem relativamente mais com o tabuleiro vazio; cavalos perdem alcance
+    pub fn value_eg(&self) -> i32 {
+        match self {
+            PieceKind::Pawn   => 120,
+            PieceKind::Knight => 300,
+            PieceKind::Bishop => 330,
+            PieceKind::Rook   => 540,
+            PieceKind::Queen  => 920,
+            PieceKind::King   => 20000,
+        }
+    }
+
+    /// Peso da peça na fase de jogo (cavalo e bispo 1, torre 2, dama 4; peões e rei não contam)
+    pub fn phase_weight(&self) -> i32 {
+        match self {
+            PieceKind::Knight | PieceKind::Bishop => 1,
+            PieceKind::Rook => 2,
+            PieceKind::Queen => 4,
+            PieceKind::Pawn | PieceKind::King => 0,
+        }
+    }
+
+    /// Valor interpolado entre `value_eg` (fase 0) e `value_mg` (fase `MAX_PHASE`)
+    /// Fases fora do intervalo são limitadas a [0, MAX_PHASE]
+    pub fn tapered_value(&self, phase: i32) -> i32 {
+        let phase = phase.clamp(0, MAX_PHASE);
+        (self.value_mg() * phase + self.value_eg() * (MAX_PHASE - phase)) / MAX_PHASE
+    }
+}
+
+/// Fase de jogo com o material inicial completo (4 cavalos e bispos, 4 torres, 2 damas)
+pub const MAX_PHASE: i32 = 24;
+
+/// Letra FEN da peça: maiúscula para as brancas, minúscula para as pretas
+pub fn fen_char(kind: PieceKind, color: Color) -> char {
+    match color {
+        Color::White => kind.to_char().to_ascii_uppercase(),
+        Color::Black => kind.to_char(),
+    }
+}
+
+/// Campo de colocação das peças da FEN a partir da peça de cada casa (a1 = índice 0)
+pub fn fen_placement(squares: &[Option<Piece>; 64]) -> String {
+    let mut placement = String::new();
+    for rank in (0..8u8).rev() {
+        let mut empty = 0;
+        for file in 0..8u8 {
+            match squares[(rank * 8 + file) as usize] {
+                Some(piece) => {
+                    if empty > 0 {
+                        placement.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    placement.push(fen_char(piece.kind, piece.color));
+                }
+                None => empty += 1,
+            }
+        }
+        if empty > 0 {
+            placement.push_str(&empty.to_string());
+        }
+        if rank > 0 {
+            placement.push('/');
+        }
+    }
+    placement
+}
+
+// ============================================================================
+// RESULTADO DO JOGO
+// ============================================================================
+
+/// Motivo de um empate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawReason {
+    Stalemate,
+    FiftyMove,
+    /// Só devolvido por `Board::outcome_with_history`: o tabuleiro não guarda histórico
+    Repetition,
+    InsufficientMaterial,
+}
+
+/// Estado de uma partida a partir da posição atual
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    Ongoing,
+    WhiteWins,
+    BlackWins,
+    Draw(DrawReason),
 }
 
 // ============================================================================
@@ -64,8 +211,16 @@ pub struct UndoInfo {
     pub old_zobrist_hash: u64,
     pub old_white_king_in_check: bool,
     pub old_black_king_in_check: bool,
+    pub old_check_pending: bool,
 }
 
+/// Estado necessário para desfazer um lance nulo
+#[derive(Debug, Clone, Copy)]
+pub struct NullUndo {
+    pub old_en_passant_target: Option<u8>,
+    pub old_halfmove_clock: u16,
+    pub old_zobrist_hash: u64,
+}
 
 // Struct para representar uma peça no tabuleiro, combinando o tipo e a cor.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -95,32 +250,204 @@ pub struct Move {
     pub is_en_passant: bool,
 }
 
+impl Move {
+    /// Lance nulo (a1a1): sentinela para "nenhum lance", nunca emitido pelos geradores
+    #[inline(always)]
+    pub const fn null() -> Move {
+        Move { from: 0, to: 0, promotion: None, is_castling: false, is_en_passant: false }
+    }
+
+    /// Verifica se é o lance nulo
+    /// Compara o lance inteiro: em Chess960 um roque pode ter origem igual ao destino
+    #[inline(always)]
+    pub fn is_null(&self) -> bool {
+        *self == Move::null()
+    }
+}
+
+// ============================================================================
+// LISTA DE LANCES SEM ALOCAÇÃO
+// ============================================================================
+
+/// Capacidade máxima de uma `MoveList` (nenhuma posição legal tem mais de 218 lances)
+pub const MAX_MOVES: usize = 256;
+
+/// Destino dos geradores de lances: `Vec<Move>` ou `MoveList`
+pub trait MoveBuffer {
+    fn push(&mut self, mv: Move);
+    fn clear(&mut self);
+}
+
+impl MoveBuffer for Vec<Move> {
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        Vec::push(self, mv);
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+}
+
+/// Lista de lances de capacidade fixa guardada na stack (sem alocação no heap)
+/// Acede-se como um slice: `len`, `iter`, indexação e `sort_by` vêm de `Deref<Target = [Move]>`
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        MoveList { moves: [Move::null(); MAX_MOVES], len: 0 }
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MoveBuffer for MoveList {
+    /// Entra em pânico se a lista já tiver `MAX_MOVES` lances
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        self.moves[self.len] = mv;
+        self.len += 1;
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl std::ops::Deref for MoveList {
+    type Target = [Move];
+
+    #[inline(always)]
+    fn deref(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+}
+
+impl std::ops::DerefMut for MoveList {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Lance compactado em 16 bits: origem (6) | destino (6) | flags (4).
+// Usado onde lances são guardados em massa (TT, killers) para poupar memória.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedMove(pub u16);
+
+const PACKED_FLAG_CASTLING: u16 = 0b0001;
+const PACKED_FLAG_EN_PASSANT: u16 = 0b0010;
+const PACKED_FLAG_PROMOTION: u16 = 0b1000; // Bits 0-1 indicam a peça: N, B, R, Q
+
+impl PackedMove {
+    #[inline(always)]
+    pub fn from_square(self) -> u8 {
+        (self.0 & 0x3F) as u8
+    }
+
+    #[inline(always)]
+    pub fn to_square(self) -> u8 {
+        ((self.0 >> 6) & 0x3F) as u8
+    }
+
+    #[inline(always)]
+    pub fn flags(self) -> u16 {
+        self.0 >> 12
+    }
+}
+
+impl From<Move> for PackedMove {
+    #[inline(always)]
+    fn from(mv: Move) -> Self {
+        let flags = match mv.promotion {
+            Some(PieceKind::Knight) => PACKED_FLAG_PROMOTION,
+            Some(PieceKind::Bishop) => PACKED_FLAG_PROMOTION | 1,
+            Some(PieceKind::Rook) => PACKED_FLAG_PROMOTION | 2,
+            Some(_) => PACKED_FLAG_PROMOTION | 3,
+            None if mv.is_castling => PACKED_FLAG_CASTLING,
+            None if mv.is_en_passant => PACKED_FLAG_EN_PASSANT,
+            None => 0,
+        };
+        PackedMove((mv.from as u16 & 0x3F) | ((mv.to as u16 & 0x3F) << 6) | (flags << 12))
+    }
+}
+
+impl TryFrom<PackedMove> for Move {
+    type Error = String;
+
+    fn try_from(packed: PackedMove) -> Result<Self, Self::Error> {
+        let flags = packed.flags();
+        let (promotion, is_castling, is_en_passant) = match flags {
+            0 => (None, false, false),
+            PACKED_FLAG_CASTLING => (None, true, false),
+            PACKED_FLAG_EN_PASSANT => (None, false, true),
+            f if f & PACKED_FLAG_PROMOTION != 0 && f & 0b0100 == 0 => {
+                let kind = match f & 0b11 {
+                    0 => PieceKind::Knight,
+                    1 => PieceKind::Bishop,
+                    2 => PieceKind::Rook,
+                    _ => PieceKind::Queen,
+                };
+                (Some(kind), false, false)
+            }
+            _ => return Err(format!("Invalid packed move flags: {:#06b}", flags)),
+        };
+
+        Ok(Move {
+            from: packed.from_square(),
+            to: packed.to_square(),
+            promotion,
+            is_castling,
+            is_en_passant,
+        })
+    }
+}
+
 // Adicione esta implementação para a struct Move
 impl std::fmt::Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Notação UCI do lance nulo
+        if self.is_null() {
+            return write!(f, "0000");
+        }
         let from = to_algebraic(self.from);
         let to = to_algebraic(self.to);
         if let Some(p) = self.promotion {
-            write!(f, "{}{}{}", from, to, piece_to_char(p))
+            write!(f, "{}{}{}", from, to, p.to_char())
         } else {
             write!(f, "{}{}", from, to)
         }
     }
 }
 
-// Adicione estas duas funções auxiliares no mesmo ficheiro
-fn to_algebraic(sq: u8) -> String {
-    let file = (sq % 8) as u8 + b'a';
-    let rank = (sq / 8) as u8 + b'1';
+// Função auxiliar para a notação algébrica de uma casa
+pub(crate) fn to_algebraic(sq: u8) -> String {
+    let file = (sq % 8) + b'a';
+    let rank = (sq / 8) + b'1';
     format!("{}{}", file as char, rank as char)
-}
-
-fn piece_to_char(p: PieceKind) -> char {
-    match p {
-        PieceKind::Queen => 'q',
-        PieceKind::Rook => 'r',
-        PieceKind::Bishop => 'b',
-        PieceKind::Knight => 'n',
-        _ => ' ',
-    }
 }
\ No newline at end of file
@@ -9,7 +9,6 @@ use super::types::*;
 // Chaves para peças [cor][tipo_peça][casa]
 // Chaves para peças [cor][tipo_peça][casa]
 // Zobrist hashing para detecção de repetições - OTIMIZADO COM CHAVES PRÉ-COMPUTADAS
-use super::types::*;
 
 // ============================================================================
 // CHAVES ZOBRIST PRÉ-COMPUTADAS PARA PERFORMANCE MÁXIMA
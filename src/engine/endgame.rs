@@ -0,0 +1,464 @@
+// Ficheiro: src/engine/endgame.rs
+// Descrição: Conhecimento de finais - chave de material, bitbase KPK (Rei+Peão vs Rei), mop-up, corrida de peões e oposição.
+
+use crate::core::{Board, Color, PieceKind};
+use crate::moves::{king::get_king_attacks, pawn::get_pawn_attacks};
+use std::sync::OnceLock;
+
+/// Chave de material de KPK com as brancas como lado forte
+pub const KPK_WHITE_KEY: u64 = Board::material_key_for([1, 0, 0, 0, 0, 1], [0, 0, 0, 0, 0, 1]);
+/// Chave de material de KPK com as pretas como lado forte
+pub const KPK_BLACK_KEY: u64 = Board::material_key_for([0, 0, 0, 0, 0, 1], [1, 0, 0, 0, 0, 1]);
+
+/// Pontuação base de uma vitória conhecida em KPK (somada ao avanço do peão)
+pub const KPK_WIN_SCORE: i32 = 2000;
+
+// ============================================================================
+// BITBASE KPK - GERADA POR ANÁLISE RETRÓGRADA NA PRIMEIRA CONSULTA
+// ============================================================================
+
+// Índice: rei forte (6 bits) | rei fraco (6 bits) | lado a jogar (1 bit) | coluna do peão a-d (2 bits) | 7ª - linha (3 bits)
+const KPK_SIZE: usize = 2 * 24 * 64 * 64;
+
+const INVALID: u8 = 0;
+const UNKNOWN: u8 = 1;
+const DRAW: u8 = 2;
+const WIN: u8 = 4;
+
+static KPK_BITBASE: OnceLock<Vec<u8>> = OnceLock::new();
+
+#[inline(always)]
+fn kpk_index(strong_to_move: bool, strong_king: u8, weak_king: u8, pawn: u8) -> usize {
+    strong_king as usize
+        | (weak_king as usize) << 6
+        | (if strong_to_move { 0 } else { 1 }) << 12
+        | ((pawn % 8) as usize) << 13
+        | ((6 - pawn / 8) as usize) << 15
+}
+
+#[inline(always)]
+fn distance(a: u8, b: u8) -> u8 {
+    let rank_diff = (a / 8).abs_diff(b / 8);
+    let file_diff = (a % 8).abs_diff(b % 8);
+    rank_diff.max(file_diff)
+}
+
+/// Classificação inicial de uma posição (lado forte = brancas, peão nas colunas a-d)
+fn initial_classification(strong_to_move: bool, strong_king: u8, weak_king: u8, pawn: u8) -> u8 {
+    let pawn_attacks = get_pawn_attacks(pawn, Color::White);
+
+    if distance(strong_king, weak_king) <= 1
+        || strong_king == pawn
+        || weak_king == pawn
+        || (strong_to_move && (pawn_attacks & (1u64 << weak_king)) != 0)
+    {
+        return INVALID;
+    }
+
+    // Promoção imediata sem que o rei fraco possa capturar a nova dama
+    if strong_to_move
+        && pawn / 8 == 6
+        && strong_king != pawn + 8
+        && (distance(weak_king, pawn + 8) > 1 || distance(strong_king, pawn + 8) == 1)
+    {
+        return WIN;
+    }
+
+    if !strong_to_move {
+        let weak_moves = get_king_attacks(weak_king);
+        let strong_control = get_king_attacks(strong_king) | pawn_attacks;
+        // Afogamento ou captura do peão indefeso
+        if (weak_moves & !strong_control) == 0
+            || (weak_moves & !get_king_attacks(strong_king) & (1u64 << pawn)) != 0
+        {
+            return DRAW;
+        }
+    }
+
+    UNKNOWN
+}
+
+/// Reclassifica uma posição a partir das posições alcançáveis num lance
+fn classify(db: &[u8], strong_to_move: bool, strong_king: u8, weak_king: u8, pawn: u8) -> u8 {
+    let mut reachable = INVALID;
+
+    if strong_to_move {
+        let mut moves = get_king_attacks(strong_king);
+        while moves != 0 {
+            let to = moves.trailing_zeros() as u8;
+            moves &= moves - 1;
+            reachable |= db[kpk_index(false, to, weak_king, pawn)];
+        }
+        if pawn / 8 < 6 {
+            reachable |= db[kpk_index(false, strong_king, weak_king, pawn + 8)];
+        }
+        if pawn / 8 == 1 && pawn + 8 != strong_king && pawn + 8 != weak_king {
+            reachable |= db[kpk_index(false, strong_king, weak_king, pawn + 16)];
+        }
+    } else {
+        let mut moves = get_king_attacks(weak_king);
+        while moves != 0 {
+            let to = moves.trailing_zeros() as u8;
+            moves &= moves - 1;
+            reachable |= db[kpk_index(true, strong_king, to, pawn)];
+        }
+    }
+
+    // O lado a jogar escolhe o melhor resultado disponível
+    let (good, bad) = if strong_to_move { (WIN, DRAW) } else { (DRAW, WIN) };
+    if reachable & good != 0 {
+        good
+    } else if reachable & UNKNOWN != 0 {
+        UNKNOWN
+    } else {
+        bad
+    }
+}
+
+fn decode(index: usize) -> (bool, u8, u8, u8) {
+    let strong_king = (index & 0x3F) as u8;
+    let weak_king = ((index >> 6) & 0x3F) as u8;
+    let strong_to_move = (index >> 12) & 1 == 0;
+    let file = ((index >> 13) & 0x3) as u8;
+    let rank = 6 - ((index >> 15) & 0x7) as u8;
+    (strong_to_move, strong_king, weak_king, rank * 8 + file)
+}
+
+fn generate_kpk_bitbase() -> Vec<u8> {
+    let mut db = vec![INVALID; KPK_SIZE];
+    for (index, entry) in db.iter_mut().enumerate() {
+        let (strong_to_move, strong_king, weak_king, pawn) = decode(index);
+        *entry = initial_classification(strong_to_move, strong_king, weak_king, pawn);
+    }
+
+    // Itera até nenhuma posição desconhecida mudar de estado
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for index in 0..KPK_SIZE {
+            if db[index] != UNKNOWN {
+                continue;
+            }
+            let (strong_to_move, strong_king, weak_king, pawn) = decode(index);
+            let result = classify(&db, strong_to_move, strong_king, weak_king, pawn);
+            if result != UNKNOWN {
+                db[index] = result;
+                changed = true;
+            }
+        }
+    }
+
+    // Posições que continuam desconhecidas não podem ser forçadas: empate
+    for entry in db.iter_mut() {
+        if *entry == UNKNOWN {
+            *entry = DRAW;
+        }
+    }
+    db
+}
+
+/// Avalia exatamente um final de Rei+Peão vs Rei usando a bitbase KPK
+/// Retorna a pontuação na perspectiva do lado a jogar, ou None se a posição não for KPK
+/// (ou se o peão estiver numa fila impossível, 1ª ou 8ª)
+pub fn probe_kpk(board: &Board) -> Option<i32> {
+    let key = board.material_key();
+    let strong = if key == KPK_WHITE_KEY {
+        Color::White
+    } else if key == KPK_BLACK_KEY {
+        Color::Black
+    } else {
+        return None;
+    };
+
+    let (strong_pieces, weak_pieces) = if strong == Color::White {
+        (board.white_pieces, board.black_pieces)
+    } else {
+        (board.black_pieces, board.white_pieces)
+    };
+
+    let mut strong_king = (board.kings & strong_pieces).trailing_zeros() as u8;
+    let mut weak_king = (board.kings & weak_pieces).trailing_zeros() as u8;
+    let mut pawn = board.pawns.trailing_zeros() as u8;
+
+    // Um peão na 1ª ou 8ª fila não existe num jogo e fica fora da bitbase
+    if pawn / 8 == 0 || pawn / 8 == 7 {
+        return None;
+    }
+
+    // Normaliza: lado forte joga "para cima" e o peão fica nas colunas a-d
+    if strong == Color::Black {
+        strong_king ^= 56;
+        weak_king ^= 56;
+        pawn ^= 56;
+    }
+    if pawn % 8 >= 4 {
+        strong_king ^= 7;
+        weak_king ^= 7;
+        pawn ^= 7;
+    }
+
+    let db = KPK_BITBASE.get_or_init(generate_kpk_bitbase);
+    let strong_to_move = board.to_move == strong;
+
+    if db[kpk_index(strong_to_move, strong_king, weak_king, pawn)] != WIN {
+        return Some(0);
+    }
+
+    let score = KPK_WIN_SCORE + (pawn / 8) as i32 * PieceKind::Pawn.value() / 10;
+    Some(if strong_to_move { score } else { -score })
+}
+
+// ============================================================================
+// MOP-UP - EMPURRA O REI FRACO PARA O CANTO EM FINAIS SEM PEÕES
+// ============================================================================
+
+/// Vantagem material mínima (sem contar reis) para ativar o mop-up
+pub const MOP_UP_MIN_ADVANTAGE: i32 = 400;
+
+// Bónus por casa do rei fraco: maior nos cantos, menor no centro
+#[rustfmt::skip]
+const CORNER_PROXIMITY: [i32; 64] = [
+    100, 90, 80, 70, 70, 80, 90, 100,
+     90, 70, 60, 50, 50, 60, 70,  90,
+     80, 60, 40, 30, 30, 40, 60,  80,
+     70, 50, 30, 20, 20, 30, 50,  70,
+     70, 50, 30, 20, 20, 30, 50,  70,
+     80, 60, 40, 30, 30, 40, 60,  80,
+     90, 70, 60, 50, 50, 60, 70,  90,
+    100, 90, 80, 70, 70, 80, 90, 100,
+];
+
+/// Termo de mop-up para finais sem peões com vantagem material decisiva:
+/// recompensa o rei fraco perto de um canto e os reis próximos (distância de Chebyshev)
+/// Retorna a pontuação na perspectiva do lado a jogar, ou 0 se o termo não se aplicar
+pub fn mop_up(board: &Board) -> i32 {
+    if board.pawns != 0 {
+        return 0;
+    }
+
+    let advantage = board.material_balance();
+    if advantage.abs() < MOP_UP_MIN_ADVANTAGE {
+        return 0;
+    }
+
+    let strong = if advantage > 0 { Color::White } else { Color::Black };
+    let strong_king = board.pieces_of_kind(strong, PieceKind::King);
+    let weak_king = board.pieces_of_kind(!strong, PieceKind::King);
+    if strong_king == 0 || weak_king == 0 {
+        return 0;
+    }
+
+    let strong_king = strong_king.trailing_zeros() as u8;
+    let weak_king = weak_king.trailing_zeros() as u8;
+    let score = CORNER_PROXIMITY[weak_king as usize] + 20 * (7 - distance(strong_king, weak_king) as i32);
+
+    if board.to_move == strong { score } else { -score }
+}
+
+// ============================================================================
+// ESCALA DE FINAIS EMPATADOS (BISPOS DE CORES OPOSTAS, TORRES NUM SÓ FLANCO)
+// ============================================================================
+
+/// Fator de escala neutro (a pontuação fica inalterada)
+pub const SCALE_NORMAL: i32 = 64;
+/// Fator de escala para padrões de material conhecidos como muito empatados
+pub const SCALE_DRAWISH: i32 = 32;
+
+const DARK_SQUARES: u64 = 0xAA55_AA55_AA55_AA55;
+const QUEENSIDE: u64 = 0x0F0F_0F0F_0F0F_0F0F;
+const KINGSIDE: u64 = 0xF0F0_F0F0_F0F0_F0F0;
+
+/// Fator de escala (em 64 avos) a aplicar à avaliação final
+pub fn drawish_scale_factor(board: &Board) -> i32 {
+    let minors_and_majors = |color| {
+        [PieceKind::Knight, PieceKind::Bishop, PieceKind::Rook, PieceKind::Queen]
+            .map(|kind| board.piece_count(color, kind))
+    };
+    let white = minors_and_majors(Color::White);
+    let black = minors_and_majors(Color::Black);
+    let pawn_difference = board.piece_count(Color::White, PieceKind::Pawn)
+        .abs_diff(board.piece_count(Color::Black, PieceKind::Pawn));
+
+    // Só bispos de cores opostas (um de cada lado) e peões
+    if white == [0, 1, 0, 0] && black == [0, 1, 0, 0] {
+        let white_bishop = board.pieces_of_kind(Color::White, PieceKind::Bishop);
+        let black_bishop = board.pieces_of_kind(Color::Black, PieceKind::Bishop);
+        let white_on_dark = (white_bishop & DARK_SQUARES) != 0;
+        let black_on_dark = (black_bishop & DARK_SQUARES) != 0;
+        if white_on_dark != black_on_dark && pawn_difference <= 2 {
+            return SCALE_DRAWISH;
+        }
+    }
+
+    // Final de torres com todos os peões no mesmo flanco e no máximo um peão de diferença
+    if white == [0, 0, 1, 0] && black == [0, 0, 1, 0] && pawn_difference <= 1 {
+        let pawns = board.pawns;
+        if pawns != 0 && ((pawns & !QUEENSIDE) == 0 || (pawns & !KINGSIDE) == 0) {
+            return SCALE_DRAWISH;
+        }
+    }
+
+    SCALE_NORMAL
+}
+
+/// Escala a pontuação em direção a zero nos finais empatados, sem nunca inverter o sinal
+pub fn scale_drawish(board: &Board, score: i32) -> i32 {
+    score * drawish_scale_factor(board) / SCALE_NORMAL
+}
+
+// ============================================================================
+// CORRIDA DE PEÕES - PEÕES PASSADOS QUE O REI ADVERSÁRIO NÃO APANHA
+// ============================================================================
+
+/// Bónus para o lado que promove um peão imparável antes do adversário
+pub const UNSTOPPABLE_PASSER_BONUS: i32 = 800;
+
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+
+/// Casas à frente do peão na própria coluna e nas adjacentes
+fn passed_pawn_span(square: u8, color: Color) -> u64 {
+    let file = square % 8;
+    let files = (FILE_A << file)
+        | if file > 0 { FILE_A << (file - 1) } else { 0 }
+        | if file < 7 { FILE_A << (file + 1) } else { 0 };
+    let ahead = if color == Color::White {
+        // Na 8ª fila não há casas à frente (e o deslocamento seria de 64 bits)
+        if square / 8 == 7 { return 0; }
+        !((1u64 << ((square / 8 + 1) * 8)) - 1)
+    } else {
+        (1u64 << ((square / 8) * 8)) - 1
+    };
+    files & ahead
+}
+
+/// Lances que o peão passado mais rápido de `color` precisa para promover,
+/// considerando só os peões fora do quadrado do rei adversário (regra do quadrado).
+/// `color_to_move` indica se `color` joga agora: caso contrário o rei adversário ganha um tempo.
+fn fastest_unstoppable_passer(board: &Board, color: Color, color_to_move: bool) -> Option<u8> {
+    let enemy_king = board.pieces_of_kind(!color, PieceKind::King);
+    if enemy_king == 0 {
+        return None;
+    }
+    let enemy_king = enemy_king.trailing_zeros() as u8;
+    let enemy_pawns = board.pieces_of_kind(!color, PieceKind::Pawn);
+    let occupied = board.occupied();
+
+    let mut fastest: Option<u8> = None;
+    let mut pawns = board.pieces_of_kind(color, PieceKind::Pawn);
+    while pawns != 0 {
+        let square = pawns.trailing_zeros() as u8;
+        pawns &= pawns - 1;
+
+        // Peões na 1ª ou 8ª fila (aceites pela FEN) não entram na corrida
+        if square / 8 == 0 || square / 8 == 7 {
+            continue;
+        }
+        if (enemy_pawns & passed_pawn_span(square, color)) != 0 {
+            continue;
+        }
+
+        let (promotion_square, start_rank) = if color == Color::White {
+            (56 + square % 8, 1)
+        } else {
+            (square % 8, 6)
+        };
+        let mut moves = (square / 8).abs_diff(promotion_square / 8);
+        if square / 8 == start_rank {
+            moves -= 1; // Avanço duplo
+        }
+        // Uma peça no caminho (o próprio rei) custa pelo menos um lance a sair
+        let path = passed_pawn_span(square, color) & (FILE_A << (square % 8));
+        if (path & occupied) != 0 {
+            moves += 1;
+        }
+
+        let king_moves = distance(enemy_king, promotion_square) as i32 - if color_to_move { 0 } else { 1 };
+        if king_moves > moves as i32 && fastest.is_none_or(|best| moves < best) {
+            fastest = Some(moves);
+        }
+    }
+    fastest
+}
+
+/// Estima a corrida de promoção em finais só com reis e peões
+/// Um lado com um peão passado imparável que promove antes do adversário recebe um bónus grande
+/// Retorna a pontuação na perspectiva do lado a jogar, ou 0 se o termo não se aplicar
+pub fn pawn_race(board: &Board) -> i32 {
+    if (board.knights | board.bishops | board.rooks | board.queens) != 0 {
+        return 0;
+    }
+
+    let us = board.to_move;
+    let ours = fastest_unstoppable_passer(board, us, true);
+    let theirs = fastest_unstoppable_passer(board, !us, false);
+
+    // Quem joga promove no meio-lance 2n - 1, o adversário no meio-lance 2m
+    match (ours, theirs) {
+        (Some(_), None) => UNSTOPPABLE_PASSER_BONUS,
+        (None, Some(_)) => -UNSTOPPABLE_PASSER_BONUS,
+        // Com um lance inteiro de vantagem a dama nova chega antes de o adversário promover
+        (Some(n), Some(m)) if n < m => UNSTOPPABLE_PASSER_BONUS,
+        (Some(n), Some(m)) if m + 1 < n => -UNSTOPPABLE_PASSER_BONUS,
+        _ => 0,
+    }
+}
+
+// ============================================================================
+// OPOSIÇÃO - REIS FRENTE A FRENTE EM FINAIS DE REIS E PEÕES
+// ============================================================================
+
+/// Bónus para o lado que tem a oposição junto a um peão
+pub const OPPOSITION_BONUS: i32 = 60;
+
+/// Termo de oposição para finais só com reis e peões
+/// Os reis estão em oposição quando ficam na mesma coluna com um número ímpar de casas entre eles;
+/// quem a tem é o lado que não joga. Só conta com um peão na coluna dos reis ou numa adjacente
+/// cujo rei esteja à frente dele e entre ele e o rei adversário; recompensa tanto o atacante
+/// que ganha a oposição à frente do peão como o defensor que a mantém.
+/// Retorna a pontuação na perspectiva do lado a jogar, ou 0 se o termo não se aplicar
+pub fn opposition(board: &Board) -> i32 {
+    if (board.knights | board.bishops | board.rooks | board.queens) != 0 || board.pawns == 0 {
+        return 0;
+    }
+
+    let white_king = board.pieces_of_kind(Color::White, PieceKind::King);
+    let black_king = board.pieces_of_kind(Color::Black, PieceKind::King);
+    if white_king == 0 || black_king == 0 {
+        return 0;
+    }
+    let white_king = white_king.trailing_zeros() as u8;
+    let black_king = black_king.trailing_zeros() as u8;
+
+    let file = white_king % 8;
+    let rank_gap = (white_king / 8).abs_diff(black_king / 8);
+    if black_king % 8 != file || rank_gap < 2 || !rank_gap.is_multiple_of(2) {
+        return 0;
+    }
+
+    let nearby_files = (FILE_A << file)
+        | if file > 0 { FILE_A << (file - 1) } else { 0 }
+        | if file < 7 { FILE_A << (file + 1) } else { 0 };
+
+    // Só interessa um peão que esteja atrás do próprio rei, com o rei adversário mais à frente
+    let white_rank = white_king / 8;
+    let black_rank = black_king / 8;
+    let mut white_pawns = board.pieces_of_kind(Color::White, PieceKind::Pawn) & nearby_files;
+    let mut black_pawns = board.pieces_of_kind(Color::Black, PieceKind::Pawn) & nearby_files;
+    let mut relevant = false;
+    while white_pawns != 0 && !relevant {
+        let rank = (white_pawns.trailing_zeros() / 8) as u8;
+        white_pawns &= white_pawns - 1;
+        relevant = rank < white_rank && white_rank < black_rank;
+    }
+    while black_pawns != 0 && !relevant {
+        let rank = (black_pawns.trailing_zeros() / 8) as u8;
+        black_pawns &= black_pawns - 1;
+        relevant = rank > black_rank && black_rank > white_rank;
+    }
+    if !relevant {
+        return 0;
+    }
+
+    // O lado a jogar tem de ceder terreno: a oposição pertence ao adversário
+    -OPPOSITION_BONUS
+}
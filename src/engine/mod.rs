@@ -1,3 +1,7 @@
+pub mod endgame;
+pub mod perft;
 pub mod perft_tt;
 
-pub use perft_tt::*;
\ No newline at end of file
+pub use endgame::*;
+pub use perft::*;
+pub use perft_tt::*;
@@ -0,0 +1,58 @@
+// Ficheiro: src/engine/perft.rs
+// Descrição: Contagem de nós perft para validar a geração de lances.
+
+use crate::core::{Board, Move, MoveList};
+
+/// Conta os nós folha até a profundidade indicada (apenas lances legais)
+pub fn perft(board: &mut Board, depth: u8) -> u64 {
+    // Uma lista de lances por ply, reutilizada em todos os nós desse ply
+    let mut buffers = vec![MoveList::new(); depth as usize];
+    perft_with_buffers(board, depth, &mut buffers)
+}
+
+fn perft_with_buffers(board: &mut Board, depth: u8, buffers: &mut [MoveList]) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let (moves, deeper) = buffers.split_first_mut().expect("um buffer por ply");
+    board.generate_all_moves_into(moves); // pseudo-legais
+
+    if depth == 1 {
+        // Bulk counting: conta os legais sem descer mais um nível
+        return moves.iter().filter(|&&mv| board.is_legal_move(mv)).count() as u64;
+    }
+
+    let mut nodes = 0;
+    for &mv in moves.iter() {
+        let undo_info = board.make_move_with_undo(mv);
+        if !board.is_king_in_check(!board.to_move) {
+            nodes += perft_with_buffers(board, depth - 1, deeper);
+        }
+        board.unmake_move(mv, undo_info);
+    }
+    nodes
+}
+
+/// Perft dividido: número de nós abaixo de cada lance legal da raiz
+pub fn perft_divide(board: &mut Board, depth: u8) -> Vec<(Move, u64)> {
+    #[cfg(feature = "profiling")]
+    let _timer = crate::profiling::get_profiler().start_timer("perft_divide");
+
+    let mut results = Vec::new();
+    if depth == 0 {
+        return results;
+    }
+
+    for mv in board.generate_all_moves() {
+        let undo_info = board.make_move_with_undo(mv);
+        if !board.is_king_in_check(!board.to_move) {
+            let nodes = perft(board, depth - 1);
+            #[cfg(feature = "profiling")]
+            crate::count!("perft_nodes", nodes);
+            results.push((mv, nodes));
+        }
+        board.unmake_move(mv, undo_info);
+    }
+    results
+}
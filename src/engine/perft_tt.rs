@@ -2,17 +2,30 @@ use std::collections::HashMap;
 
 /// Transposition Table para cache de resultados perft
 pub struct PerftTT {
-    table: HashMap<(u64, u8), u64>, // (zobrist_hash, depth) -> nodes
+    table: HashMap<(u64, u8), u64>, // (tt_key, depth) -> nodes
     hits: u64,
     misses: u64,
+    capacity: usize, // Entradas previstas, base do `hashfull_permille`
+}
+
+impl Default for PerftTT {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PerftTT {
     pub fn new() -> Self {
+        Self::with_capacity(2_000_000) // ~16MB cache
+    }
+
+    /// Tabela com espaço reservado para `capacity` entradas
+    pub fn with_capacity(capacity: usize) -> Self {
         PerftTT {
-            table: HashMap::with_capacity(2_000_000), // ~16MB cache
+            table: HashMap::with_capacity(capacity),
             hits: 0,
             misses: 0,
+            capacity: capacity.max(1),
         }
     }
     
@@ -42,4 +55,15 @@ impl PerftTT {
     pub fn misses(&self) -> u64 {
         self.misses
     }
-}
\ No newline at end of file
+
+    /// Número de entradas guardadas
+    pub fn entries_used(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Ocupação em milésimos da capacidade configurada (0-1000, como o `hashfull` do UCI)
+    /// A tabela cresce para lá da capacidade, por isso o valor satura em 1000
+    pub fn hashfull_permille(&self) -> u32 {
+        (self.table.len().saturating_mul(1000) / self.capacity).min(1000) as u32
+    }
+}
@@ -66,7 +66,7 @@ fn test_position(name: &str, fen: &str) {
             }
             
             // Mostra alguns movimentos
-            if moves.len() > 0 {
+            if !moves.is_empty() {
                 println!("Primeiros movimentos:");
                 for (i, mv) in moves.iter().take(5).enumerate() {
                     println!("   {}. {}", i + 1, mv);
@@ -140,7 +140,7 @@ fn perft_with_tt(board: &mut Board, depth: u8, tt: &mut PerftTT) -> u64 {
     }
 
     // Verifica cache primeiro
-    if let Some(cached_nodes) = tt.get(board.zobrist_hash, depth) {
+    if let Some(cached_nodes) = tt.get(board.tt_key(), depth) {
         return cached_nodes;
     }
 
@@ -151,7 +151,7 @@ fn perft_with_tt(board: &mut Board, depth: u8, tt: &mut PerftTT) -> u64 {
         let nodes = moves.iter()
             .filter(|&&mv| board.is_legal_move(mv))
             .count() as u64;
-        tt.insert(board.zobrist_hash, depth, nodes);
+        tt.insert(board.tt_key(), depth, nodes);
         return nodes;
     }
 
@@ -169,7 +169,7 @@ fn perft_with_tt(board: &mut Board, depth: u8, tt: &mut PerftTT) -> u64 {
     }
 
     // Cache resultado
-    tt.insert(board.zobrist_hash, depth, nodes);
+    tt.insert(board.tt_key(), depth, nodes);
     nodes
 }
 
@@ -184,7 +184,7 @@ fn perft_parallel(board: &mut Board, depth: u8) -> u64 {
     
     moves.par_iter().map(|&mv| {
         let mut board_clone = *board; // Copy barato devido ao trait Copy
-        let undo_info = board_clone.make_move_with_undo(mv);
+        let _undo_info = board_clone.make_move_with_undo(mv);
         let previous_to_move = !board_clone.to_move;
         
         if !board_clone.is_king_in_check(previous_to_move) {
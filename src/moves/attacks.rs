@@ -0,0 +1,47 @@
+// Ficheiro: src/moves/attacks.rs
+// Descrição: União de todas as casas atacadas por um lado (segurança do rei, mobilidade).
+
+use crate::{board::Board, types::{Bitboard, Color}};
+use super::{king::get_king_attacks, knight::get_knight_attacks, magic_bitboards};
+
+const FILE_A: Bitboard = 0x0101_0101_0101_0101;
+const FILE_H: Bitboard = 0x8080_8080_8080_8080;
+
+/// Bitboard de todas as casas atacadas pelas peças de `color` com a ocupação atual
+/// Inclui casas ocupadas por peças da própria cor (peças defendidas)
+pub fn all_attacks(board: &Board, color: Color) -> Bitboard {
+    let ours = board.pieces(color);
+    let occupied = board.occupied();
+
+    // Peões de uma vez, por deslocamento
+    let pawns = board.pawns & ours;
+    let mut attacks = match color {
+        Color::White => ((pawns & !FILE_A) << 7) | ((pawns & !FILE_H) << 9),
+        Color::Black => ((pawns & !FILE_A) >> 9) | ((pawns & !FILE_H) >> 7),
+    };
+
+    let mut knights = board.knights & ours;
+    while knights != 0 {
+        attacks |= get_knight_attacks(knights.trailing_zeros() as u8);
+        knights &= knights - 1;
+    }
+
+    let mut diagonal = (board.bishops | board.queens) & ours;
+    while diagonal != 0 {
+        attacks |= magic_bitboards::get_bishop_attacks_magic(diagonal.trailing_zeros() as u8, occupied);
+        diagonal &= diagonal - 1;
+    }
+
+    let mut orthogonal = (board.rooks | board.queens) & ours;
+    while orthogonal != 0 {
+        attacks |= magic_bitboards::get_rook_attacks_magic(orthogonal.trailing_zeros() as u8, occupied);
+        orthogonal &= orthogonal - 1;
+    }
+
+    let king = board.kings & ours;
+    if king != 0 {
+        attacks |= get_king_attacks(king.trailing_zeros() as u8);
+    }
+
+    attacks
+}
@@ -1,7 +1,8 @@
 // Ficheiro: src/moves/king.rs
 // Descrição: Lógica para gerar os lances do Rei.
 
-use crate::{board::Board, types::{Move, Color, Bitboard}};
+use crate::{board::Board, types::{Move, MoveBuffer, Color, Bitboard, PieceKind}};
+use super::magic_bitboards::squares_between;
 
 /// Tabela pré-computada de ataques de rei para máxima performance (1 ciclo CPU)
 /// Cada posição contém o bitboard de ataques possíveis do rei naquela casa
@@ -32,8 +33,8 @@ pub fn get_king_attacks(square: u8) -> Bitboard {
 
 /// Gera todos os lances pseudo-legais para o rei usando tabela pré-computada (ULTRA RÁPIDO)
 #[inline]
-pub fn generate_king_moves_into(board: &Board, moves: &mut Vec<Move>) {
-    let our_pieces = if board.to_move == Color::White { board.white_pieces } else { board.black_pieces };
+pub fn generate_king_moves_into<B: MoveBuffer>(board: &Board, moves: &mut B) {
+    let our_pieces = board.pieces(board.to_move);
     let our_king = board.kings & our_pieces;
 
     if our_king == 0 { return; }
@@ -47,51 +48,45 @@ pub fn generate_king_moves_into(board: &Board, moves: &mut Vec<Move>) {
         moves.push(Move { from: from_sq, to: to_sq, promotion: None, is_castling: false, is_en_passant: false });
     }
 
-    // Lógica de roque com validação completa
-    if board.to_move == Color::White {
-        // Roque pequeno das brancas (e1-g1)
-        if (board.castling_rights & 0b0001) != 0 {
-            if (board.white_pieces | board.black_pieces) & 0b01100000 == 0 {
-                if !board.is_king_in_check(Color::White) && 
-                   !board.is_square_attacked_by(5, Color::Black) && 
-                   !board.is_square_attacked_by(6, Color::Black) {
-                    moves.push(Move { from: 4, to: 6, promotion: None, is_castling: true, is_en_passant: false });
-                }
-            }
-        }
-        
-        // Roque grande das brancas (e1-c1)
-        if (board.castling_rights & 0b0010) != 0 {
-            if (board.white_pieces | board.black_pieces) & 0b00001110 == 0 {
-                if !board.is_king_in_check(Color::White) && 
-                   !board.is_square_attacked_by(3, Color::Black) && 
-                   !board.is_square_attacked_by(2, Color::Black) {
-                    moves.push(Move { from: 4, to: 2, promotion: None, is_castling: true, is_en_passant: false });
-                }
-            }
-        }
-    } else {
-        // Roque pequeno das pretas (e8-g8)
-        if (board.castling_rights & 0b0100) != 0 {
-            if (board.white_pieces | board.black_pieces) & 0x6000000000000000 == 0 {
-                if !board.is_king_in_check(Color::Black) && 
-                   !board.is_square_attacked_by(61, Color::White) && 
-                   !board.is_square_attacked_by(62, Color::White) {
-                    moves.push(Move { from: 60, to: 62, promotion: None, is_castling: true, is_en_passant: false });
-                }
-            }
-        }
-        
-        // Roque grande das pretas (e8-c8)
-        if (board.castling_rights & 0b1000) != 0 {
-            if (board.white_pieces | board.black_pieces) & 0x0e00000000000000 == 0 {
-                if !board.is_king_in_check(Color::Black) && 
-                   !board.is_square_attacked_by(59, Color::White) && 
-                   !board.is_square_attacked_by(58, Color::White) {
-                    moves.push(Move { from: 60, to: 58, promotion: None, is_castling: true, is_en_passant: false });
-                }
+    // Lógica de roque com validação completa (clássico e Chess960)
+    // Em xeque não há roque
+    let us = board.to_move;
+    if board.is_king_in_check(us) { return; }
+
+    let back_rank = if us == Color::White { 0 } else { 56 };
+    let first_right = if us == Color::White { 0 } else { 2 };
+    let our_rooks = board.pieces_of_kind(us, PieceKind::Rook);
+
+    // (índice do direito, coluna final do rei, coluna final da torre)
+    for (index, king_file, rook_file) in [(first_right, 6, 5), (first_right + 1, 2, 3)] {
+        if (board.castling_rights & (1 << index)) == 0 { continue; }
+
+        let rook_from = board.castling_rooks[index];
+        if (our_rooks & (1u64 << rook_from)) == 0 { continue; }
+
+        let king_to = back_rank + king_file;
+        let rook_to = back_rank + rook_file;
+
+        // Todas as casas percorridas pelo rei e pela torre têm de estar livres (exceto os próprios)
+        let king_path = squares_between(from_sq, king_to) | (1u64 << king_to);
+        let rook_path = squares_between(rook_from, rook_to) | (1u64 << rook_to);
+        let blockers = board.occupied() & !(1u64 << from_sq) & !(1u64 << rook_from);
+        if ((king_path | rook_path) & blockers) != 0 { continue; }
+
+        // O rei não pode passar por casas atacadas; a legalidade volta a validar a casa final,
+        // porque em Chess960 a torre que sai pode descobrir um ataque ao rei
+        let mut path = king_path & !(1u64 << from_sq);
+        let mut attacked = false;
+        while path != 0 {
+            let sq = path.trailing_zeros() as u8;
+            path &= path - 1;
+            if board.is_square_attacked_by(sq, !us) {
+                attacked = true;
+                break;
             }
         }
+        if attacked { continue; }
+
+        moves.push(Move { from: from_sq, to: king_to, promotion: None, is_castling: true, is_en_passant: false });
     }
 }
-
@@ -1,7 +1,7 @@
 // Ficheiro: src/moves/knight.rs
 // Descrição: Lógica para gerar os lances dos cavalos.
 
-use crate::{board::Board, types::{Move, Color, Bitboard}};
+use crate::{board::Board, types::{Move, MoveBuffer, Bitboard}};
 
 /// Tabela pré-computada de ataques de cavalo para máxima performance (1 ciclo CPU)
 /// Cada posição contém o bitboard de ataques possíveis do cavalo naquela casa
@@ -32,8 +32,8 @@ pub fn get_knight_attacks(square: u8) -> Bitboard {
 
 /// Gera todos os lances pseudo-legais para os cavalos usando tabela pré-computada (ULTRA RÁPIDO)
 #[inline]
-pub fn generate_knight_moves_into(board: &Board, moves: &mut Vec<Move>) {
-    let our_pieces = if board.to_move == Color::White { board.white_pieces } else { board.black_pieces };
+pub fn generate_knight_moves_into<B: MoveBuffer>(board: &Board, moves: &mut B) {
+    let our_pieces = board.pieces(board.to_move);
     let mut our_knights = board.knights & our_pieces;
 
     while our_knights != 0 {
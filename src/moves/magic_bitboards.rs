@@ -3,7 +3,9 @@
 
 use crate::types::Bitboard;
 use std::sync::OnceLock;
-use crate::utils::intrinsics::{parallel_deposit, popcount};
+use crate::utils::intrinsics::popcount;
+#[cfg(target_arch = "x86_64")]
+use crate::utils::intrinsics::parallel_deposit;
 
 // ============================================================================
 // ESTRUTURAS FUNDAMENTAIS PARA MAGIC BITBOARDS
@@ -18,7 +20,7 @@ pub struct MagicBitboard {
     pub offset: usize,
 }
 
-/// Tabela global de ataques - inicializada uma vez
+/// Tabela global de ataques - inicializada uma vez, no primeiro acesso
 static BISHOP_ATTACKS: OnceLock<Vec<Bitboard>> = OnceLock::new();
 static ROOK_ATTACKS: OnceLock<Vec<Bitboard>> = OnceLock::new();
 
@@ -175,7 +177,7 @@ fn calculate_rook_attacks(square: u8, occupancy: Bitboard) -> Bitboard {
     for (dr, df) in directions {
         let mut r = rank + dr;
         let mut f = file + df;
-        while r >= 0 && r < 8 && f >= 0 && f < 8 {
+        while (0..8).contains(&r) && (0..8).contains(&f) {
             let target = (r * 8 + f) as u8;
             let target_bb = 1u64 << target;
             result |= target_bb;
@@ -200,7 +202,7 @@ fn calculate_bishop_attacks(square: u8, occupancy: Bitboard) -> Bitboard {
         let mut r = rank + dr;
         let mut f = file + df;
 
-        while r >= 0 && r < 8 && f >= 0 && f < 8 {
+        while (0..8).contains(&r) && (0..8).contains(&f) {
             let target = (r * 8 + f) as u8;
             let target_bb = 1u64 << target;
             result |= target_bb;
@@ -323,18 +325,11 @@ fn generate_occupancies(mask: Bitboard) -> Vec<Bitboard> {
     result
 }
 
-/// Inicializa as tabelas de ataque globais
-pub fn init_magic_bitboards() {
-    // Verifica se já foi inicializado
-    if ROOK_ATTACKS.get().is_some() && BISHOP_ATTACKS.get().is_some() {
-        return;
-    }
-    // Inicializar ataques de torre
+/// Gera a tabela de ataques de torre (chamada uma única vez, no primeiro acesso)
+fn generate_rook_attack_table() -> Vec<Bitboard> {
     let mut rook_attacks = Vec::new();
-    let mut _total_size = 0;
-    
-    for square in 0..64 {
-        let magic = &ROOK_MAGICS_TABLE[square];
+
+    for (square, magic) in ROOK_MAGICS_TABLE.iter().enumerate() {
         let occupancies = generate_occupancies(magic.mask);
         let size = 1 << (64 - magic.shift);
         
@@ -346,16 +341,16 @@ pub fn init_magic_bitboards() {
         }
         
         rook_attacks.extend(attacks);
-        _total_size += size;
     }
-    
-    let _ = ROOK_ATTACKS.set(rook_attacks);
 
-    // Inicializar ataques de bispo  
+    rook_attacks
+}
+
+/// Gera a tabela de ataques de bispo (chamada uma única vez, no primeiro acesso)
+fn generate_bishop_attack_table() -> Vec<Bitboard> {
     let mut bishop_attacks = Vec::new();
     
-    for square in 0..64 {
-        let magic = &BISHOP_MAGICS_TABLE[square];
+    for (square, magic) in BISHOP_MAGICS_TABLE.iter().enumerate() {
         let occupancies = generate_occupancies(magic.mask);
         let size = 1 << (64 - magic.shift);
         
@@ -368,13 +363,22 @@ pub fn init_magic_bitboards() {
         
         bishop_attacks.extend(attacks);
     }
-    
-    let _ = BISHOP_ATTACKS.set(bishop_attacks);
+
+    bishop_attacks
+}
+
+/// Força a inicialização das tabelas de ataque globais (opcional)
+/// As tabelas são criadas sob demanda no primeiro acesso; isto apenas antecipa esse custo.
+pub fn init_magic_bitboards() {
+    ROOK_ATTACKS.get_or_init(generate_rook_attack_table);
+    BISHOP_ATTACKS.get_or_init(generate_bishop_attack_table);
 }
 
 // ============================================================================
 // FUNÇÕES PÚBLICAS DE ALTA PERFORMANCE
 // ============================================================================
+// Depois da inicialização, `get_or_init` custa uma leitura atómica e um salto previsível:
+// no perft (inicial a 5, Kiwipete a 4) não há diferença mensurável face a um acesso sem verificação
 
 /// Obtém ataques de torre usando magic bitboards (ULTRA RÁPIDO)
 #[inline(always)]
@@ -382,7 +386,7 @@ pub fn get_rook_attacks_magic(square: u8, occupancy: Bitboard) -> Bitboard {
     let magic = &ROOK_MAGICS_TABLE[square as usize];
     let index = ((occupancy & magic.mask).wrapping_mul(magic.magic)) >> magic.shift;
     
-    ROOK_ATTACKS.get().unwrap()[magic.offset + index as usize]
+    ROOK_ATTACKS.get_or_init(generate_rook_attack_table)[magic.offset + index as usize]
 }
 
 /// Obtém ataques de bispo usando magic bitboards (ULTRA RÁPIDO)
@@ -391,7 +395,7 @@ pub fn get_bishop_attacks_magic(square: u8, occupancy: Bitboard) -> Bitboard {
     let magic = &BISHOP_MAGICS_TABLE[square as usize];
     let index = ((occupancy & magic.mask).wrapping_mul(magic.magic)) >> magic.shift;
     
-    BISHOP_ATTACKS.get().unwrap()[magic.offset + index as usize]
+    BISHOP_ATTACKS.get_or_init(generate_bishop_attack_table)[magic.offset + index as usize]
 }
 
 /// Obtém ataques de rainha (combinação de torre + bispo)
@@ -411,4 +415,21 @@ pub fn is_square_attacked_by_sliding(square: u8, occupancy: Bitboard,
     
     ((rook_attacks & (enemy_rooks | enemy_queens)) != 0) ||
     ((bishop_attacks & (enemy_bishops | enemy_queens)) != 0)
-}
\ No newline at end of file
+}
+/// Obtém as casas estritamente entre duas casas alinhadas (coluna, linha ou diagonal)
+/// Retorna 0 se as casas não estiverem alinhadas
+#[inline(always)]
+pub fn squares_between(a: u8, b: u8) -> Bitboard {
+    let rank_diff = (a / 8) as i32 - (b / 8) as i32;
+    let file_diff = (a % 8) as i32 - (b % 8) as i32;
+
+    if a == b {
+        0
+    } else if rank_diff == 0 || file_diff == 0 {
+        get_rook_attacks_magic(a, 1u64 << b) & get_rook_attacks_magic(b, 1u64 << a)
+    } else if rank_diff.abs() == file_diff.abs() {
+        get_bishop_attacks_magic(a, 1u64 << b) & get_bishop_attacks_magic(b, 1u64 << a)
+    } else {
+        0
+    }
+}
@@ -1,7 +1,7 @@
 // Ficheiro: src/moves/pawn.rs
 // Descrição: Lógica para gerar os lances dos peões - OTIMIZADO COM TABELAS PRÉ-COMPUTADAS.
 
-use crate::{board::Board, types::{Move, Color, Bitboard, PieceKind}};
+use crate::{board::Board, types::{Move, MoveBuffer, Color, Bitboard, PieceKind}};
 
 // Constantes importadas ou redefinidas para este módulo
 const NOT_A_FILE: Bitboard = 0xfefefefefefefefe;
@@ -158,36 +158,17 @@ pub fn get_pawn_double_moves(square: u8, color: Color) -> Bitboard {
 }
 
 /// Obtém as posições de peões que podem atacar uma casa (reverse attacks O(1))
+/// Um peão de uma cor ataca `square` exatamente das casas que um peão da outra cor em `square` atacaria,
+/// por isso basta a tabela de ataques da cor oposta
 #[inline(always)]
 pub fn get_pawn_attackers(square: u8, attacking_color: Color) -> Bitboard {
-    let mut attackers = 0u64;
-    match attacking_color {
-        Color::White => {
-            // Peões brancos atacam diagonalmente para cima
-            if square % 8 != 0 && square >= 9 {
-                attackers |= 1u64 << (square - 9); // Ataque da esquerda
-            }
-            if square % 8 != 7 && square >= 7 {
-                attackers |= 1u64 << (square - 7); // Ataque da direita
-            }
-        }
-        Color::Black => {
-            // Peões pretos atacam diagonalmente para baixo
-            if square % 8 != 0 && square <= 54 {
-                attackers |= 1u64 << (square + 7); // Ataque da esquerda (visão preta)
-            }
-            if square % 8 != 7 && square <= 56 {
-                attackers |= 1u64 << (square + 9); // Ataque da direita
-            }
-        }
-    }
-    attackers
+    get_pawn_attacks(square, !attacking_color)
 }
 
 /// Adiciona lances de peão diretamente ao Vec (OTIMIZADO - sem alocação extra)
 #[inline]
-pub fn generate_pawn_moves_into(board: &Board, moves: &mut Vec<Move>) {
-    let all_pieces = board.white_pieces | board.black_pieces;
+pub fn generate_pawn_moves_into<B: MoveBuffer>(board: &Board, moves: &mut B) {
+    let all_pieces = board.occupied();
 
     if board.to_move == Color::White {
         let our_pawns = board.pawns & board.white_pieces;
@@ -255,7 +236,7 @@ pub fn generate_pawn_moves_into(board: &Board, moves: &mut Vec<Move>) {
 
 /// Função auxiliar para capturas de peão diretamente no Vec
 #[inline]
-fn generate_pawn_captures_into(board: &Board, moves: &mut Vec<Move>) {
+fn generate_pawn_captures_into<B: MoveBuffer>(board: &Board, moves: &mut B) {
     if board.to_move == Color::White {
         let our_pawns = board.pawns & board.white_pieces;
 
@@ -295,18 +276,14 @@ fn generate_pawn_captures_into(board: &Board, moves: &mut Vec<Move>) {
             if ep_rank == 5 {
                 if ep_target % 8 > 0 {
                     let from_sq = ep_target - 9;
-                    if from_sq / 8 == 4 {
-                        if (our_pawns & (1u64 << from_sq)) != 0 {
-                            moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
-                        }
+                    if from_sq / 8 == 4 && (our_pawns & (1u64 << from_sq)) != 0 {
+                        moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
                     }
                 }
                 if ep_target % 8 < 7 {
                     let from_sq = ep_target - 7;
-                    if from_sq / 8 == 4 {
-                        if (our_pawns & (1u64 << from_sq)) != 0 {
-                            moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
-                        }
+                    if from_sq / 8 == 4 && (our_pawns & (1u64 << from_sq)) != 0 {
+                        moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
                     }
                 }
             }
@@ -351,18 +328,14 @@ fn generate_pawn_captures_into(board: &Board, moves: &mut Vec<Move>) {
             if ep_rank == 2 {
                 if ep_target % 8 > 0 {
                     let from_sq = ep_target + 7;
-                    if from_sq / 8 == 3 {
-                        if (our_pawns & (1u64 << from_sq)) != 0 {
-                            moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
-                        }
+                    if from_sq / 8 == 3 && (our_pawns & (1u64 << from_sq)) != 0 {
+                        moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
                     }
                 }
                 if ep_target % 8 < 7 {
                     let from_sq = ep_target + 9;
-                    if from_sq / 8 == 3 {
-                        if (our_pawns & (1u64 << from_sq)) != 0 {
-                            moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
-                        }
+                    if from_sq / 8 == 3 && (our_pawns & (1u64 << from_sq)) != 0 {
+                        moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
                     }
                 }
             }
@@ -418,18 +391,14 @@ pub fn generate_pawn_captures(board: &Board) -> Vec<Move> {
             if ep_rank == 5 {
                 if ep_target % 8 > 0 {
                     let from_sq = ep_target - 9;
-                    if from_sq / 8 == 4 {
-                        if (our_pawns & (1u64 << from_sq)) != 0 {
-                            moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
-                        }
+                    if from_sq / 8 == 4 && (our_pawns & (1u64 << from_sq)) != 0 {
+                        moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
                     }
                 }
                 if ep_target % 8 < 7 {
                     let from_sq = ep_target - 7;
-                    if from_sq / 8 == 4 {
-                        if (our_pawns & (1u64 << from_sq)) != 0 {
-                            moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
-                        }
+                    if from_sq / 8 == 4 && (our_pawns & (1u64 << from_sq)) != 0 {
+                        moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
                     }
                 }
             }
@@ -474,18 +443,14 @@ pub fn generate_pawn_captures(board: &Board) -> Vec<Move> {
             if ep_rank == 2 {
                 if ep_target % 8 > 0 {
                     let from_sq = ep_target + 7;
-                    if from_sq / 8 == 3 {
-                        if (our_pawns & (1u64 << from_sq)) != 0 {
-                            moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
-                        }
+                    if from_sq / 8 == 3 && (our_pawns & (1u64 << from_sq)) != 0 {
+                        moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
                     }
                 }
                 if ep_target % 8 < 7 {
                     let from_sq = ep_target + 9;
-                    if from_sq / 8 == 3 {
-                        if (our_pawns & (1u64 << from_sq)) != 0 {
-                            moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
-                        }
+                    if from_sq / 8 == 3 && (our_pawns & (1u64 << from_sq)) != 0 {
+                        moves.push(Move { from: from_sq, to: ep_target, promotion: None, is_castling: false, is_en_passant: true });
                     }
                 }
             }
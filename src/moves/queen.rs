@@ -1,14 +1,14 @@
 // Ficheiro: src/moves/queen.rs
 // Descrição: Lógica para gerar os lances da Dama - OTIMIZADO COM MAGIC BITBOARDS.
 
-use crate::{board::Board, types::{Move, Color, Bitboard}};
+use crate::{board::Board, types::{Move, MoveBuffer, Bitboard}};
 use super::magic_bitboards::get_queen_attacks_magic;
 
 /// Gera todos os lances pseudo-legais para a dama do jogador atual (PERFORMANCE OTIMIZADA)
 #[inline]
-pub fn generate_queen_moves_into(board: &Board, moves: &mut Vec<Move>) {
-    let our_pieces = if board.to_move == Color::White { board.white_pieces } else { board.black_pieces };
-    let all_pieces = board.white_pieces | board.black_pieces;
+pub fn generate_queen_moves_into<B: MoveBuffer>(board: &Board, moves: &mut B) {
+    let our_pieces = board.pieces(board.to_move);
+    let all_pieces = board.occupied();
     let mut our_queens = board.queens & our_pieces;
 
     while our_queens != 0 {
@@ -50,6 +50,12 @@ impl TimerStats {
     }
 }
 
+impl Default for PerformanceProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PerformanceProfiler {
     pub fn new() -> Self {
         PerformanceProfiler {
@@ -68,7 +74,7 @@ impl PerformanceProfiler {
     }
 
     /// Inicia timer para uma função/operação
-    pub fn start_timer(&self, name: &str) -> TimerHandle {
+    pub fn start_timer(&self, name: &str) -> TimerHandle<'_> {
         TimerHandle {
             name: if self.enabled { name.to_string() } else { String::new() },
             start: Instant::now(),
@@ -104,6 +110,13 @@ impl PerformanceProfiler {
         counter.fetch_add(value, Ordering::Relaxed);
     }
 
+    /// Valor atual de um contador (0 se nunca foi incrementado)
+    pub fn counter_value(&self, name: &str) -> u64 {
+        self.counters.lock().unwrap()
+            .get(name)
+            .map_or(0, |counter| counter.load(Ordering::Relaxed))
+    }
+
     /// Gera relatório de performance (versão simplificada para evitar loops)
     pub fn generate_report(&self) -> String {
         let mut report = String::new();
@@ -113,7 +126,7 @@ impl PerformanceProfiler {
         if let Ok(timers) = self.timers.try_lock() {
             report.push_str("TEMPOS DE EXECUÇÃO:\n");
             let mut timer_vec: Vec<_> = timers.iter().take(10).collect(); // Limita a 10 entradas
-            timer_vec.sort_by(|a, b| b.1.total_time.cmp(&a.1.total_time));
+            timer_vec.sort_by_key(|entry| std::cmp::Reverse(entry.1.total_time));
 
             for (name, stats) in timer_vec {
                 report.push_str(&format!(
@@ -124,7 +137,7 @@ impl PerformanceProfiler {
                     stats.average().as_micros()
                 ));
             }
-            report.push_str("\n");
+            report.push('\n');
         } else {
             report.push_str("TIMERS: Bloqueado ou em uso\n\n");
         }
@@ -144,7 +157,7 @@ impl PerformanceProfiler {
                     counter.load(Ordering::Relaxed)
                 ));
             }
-            report.push_str("\n");
+            report.push('\n');
         } else {
             report.push_str("CONTADORES: Bloqueado ou em uso\n\n");
         }
@@ -159,6 +172,7 @@ impl PerformanceProfiler {
     }
 
     /// Analisa gargalos e gera recomendações (versão simplificada)
+    #[allow(dead_code)]
     fn analyze_bottlenecks(&self, report: &mut String) {
         // Versão simplificada para evitar deadlocks
         report.push_str("DICAS DE OTIMIZAÇÃO:\n");
@@ -213,7 +227,7 @@ static PROFILER: OnceLock<PerformanceProfiler> = OnceLock::new();
 
 /// Acessa o profiler global
 pub fn get_profiler() -> &'static PerformanceProfiler {
-    PROFILER.get_or_init(|| PerformanceProfiler::new())
+    PROFILER.get_or_init(PerformanceProfiler::new)
 }
 
 /// Macro para facilitar o profiling
@@ -221,7 +235,7 @@ pub fn get_profiler() -> &'static PerformanceProfiler {
 macro_rules! profile {
     ($name:expr, $code:block) => {
         {
-            let _timer = crate::profiling::get_profiler().start_timer($name);
+            let _timer = $crate::profiling::get_profiler().start_timer($name);
             $code
         }
     };
@@ -231,10 +245,10 @@ macro_rules! profile {
 #[macro_export]
 macro_rules! count {
     ($name:expr) => {
-        crate::profiling::PROFILER.increment_counter($name);
+        $crate::profiling::get_profiler().increment_counter($name);
     };
     ($name:expr, $value:expr) => {
-        crate::profiling::PROFILER.add_to_counter($name, $value);
+        $crate::profiling::get_profiler().add_to_counter($name, $value);
     };
 }
 
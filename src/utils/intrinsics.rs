@@ -286,6 +286,7 @@ impl Iterator for BitboardIterator {
 }
 
 /// Trait para operações de bitboard otimizadas
+#[allow(clippy::wrong_self_convention)]
 pub trait BitboardOps {
     fn iter_squares(self) -> BitboardIterator;
     fn popcount_fast(self) -> u32;
@@ -395,7 +396,7 @@ pub fn get_occupancy_index(occupancy: Bitboard, mask: Bitboard) -> u64 {
     {
         // Fallback eficiente para outras arquiteturas
         let mut index = 0u64;
-        let mut occ = occupancy & mask;
+        let occ = occupancy & mask;
         let mut m = mask;
         let mut bit = 0;
         
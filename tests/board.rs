@@ -0,0 +1,916 @@
+// Testes da API do tabuleiro: make/unmake, FEN, legalidade e consultas de posição.
+
+use std::collections::HashMap;
+
+use pelanca::{fen_char, Bitboard, Board, BoardBuilder, Color, DrawReason, GameResult, Move, Piece, PieceKind};
+
+fn assert_mailbox_consistent(board: &Board) {
+    for square in 0..64u8 {
+        let bb = 1u64 << square;
+        let expected = [
+            (board.pawns, PieceKind::Pawn),
+            (board.knights, PieceKind::Knight),
+            (board.bishops, PieceKind::Bishop),
+            (board.rooks, PieceKind::Rook),
+            (board.queens, PieceKind::Queen),
+            (board.kings, PieceKind::King),
+        ]
+        .iter()
+        .find(|(pieces, _)| pieces & bb != 0)
+        .map(|&(_, kind)| {
+            let color = if board.white_pieces & bb != 0 { Color::White } else { Color::Black };
+            Piece::new(kind, color)
+        });
+        assert_eq!(board.mailbox[square as usize], expected, "casa {}\n{}", square, board);
+    }
+}
+
+// Estado que unmake_move tem de restaurar exatamente
+fn undoable_state(board: &Board) -> (Bitboard, Bitboard, (u16, u16), Option<u8>, u8, u64) {
+    (
+        board.white_pieces,
+        board.black_pieces,
+        (board.halfmove_clock, board.fullmove_number),
+        board.en_passant_target,
+        board.castling_rights,
+        board.zobrist_hash,
+    )
+}
+
+fn walk_make_unmake(board: &mut Board, depth: u8) {
+    assert_mailbox_consistent(board);
+    if depth == 0 {
+        return;
+    }
+    for mv in board.generate_legal_moves() {
+        assert!(!mv.is_null(), "gerador emitiu o lance nulo");
+        let before = (board.mailbox, undoable_state(board));
+        let undo = board.make_move_with_undo(mv);
+        walk_make_unmake(board, depth - 1);
+        board.unmake_move(mv, undo);
+        assert_eq!((board.mailbox, undoable_state(board)), before, "unmake de {}", mv);
+    }
+}
+
+#[test]
+fn mailbox_matches_bitboards() {
+    // O mailbox tem de acompanhar os bitboards em todos os lances especiais (roque, en passant, promoção)
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+    ];
+    for fen in fens {
+        let mut board = Board::from_fen(fen).expect("FEN inválido");
+        walk_make_unmake(&mut board, 3);
+        assert_mailbox_consistent(&board.flip());
+    }
+}
+
+// Com `--features hash-check`, make/unmake (normais e nulos) entram em pânico se o hash incremental divergir
+#[cfg(feature = "hash-check")]
+#[test]
+fn zobrist_hash_never_drifts() {
+    fn walk(board: &mut Board, depth: u8) {
+        if depth == 0 {
+            return;
+        }
+        for mv in board.generate_legal_moves() {
+            let undo = board.make_move_with_undo(mv);
+            if !board.is_king_in_check(board.to_move) {
+                let null = board.make_null_move();
+                board.unmake_null_move(null);
+            }
+            walk(board, depth - 1);
+            board.unmake_move(mv, undo);
+        }
+    }
+
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+    ];
+    for fen in fens {
+        walk(&mut Board::from_fen(fen).expect("FEN inválido"), 3);
+    }
+}
+
+#[test]
+fn unmake_restores_halfmove_clock() {
+    // Um lance de cavalo incrementa o relógio dos 50 lances; desfazê-lo tem de repor o valor anterior
+    let mut board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 7 1")
+        .expect("FEN inválido");
+    let mv = board.parse_uci_move("e5d3").expect("lance legal");
+    let undo = board.make_move_with_undo(mv);
+    assert_eq!(board.halfmove_clock, 8);
+    board.unmake_move(mv, undo);
+    assert_eq!(board.halfmove_clock, 7);
+    assert_eq!(board.castling_rights, 0b1111);
+}
+
+#[test]
+fn null_move_sentinel() {
+    assert!(Move::null().is_null());
+    assert_eq!(Move::null().to_string(), "0000");
+    let board = Board::new();
+    let mv = board.parse_uci_move("e2e4").expect("lance legal");
+    assert!(!mv.is_null());
+}
+
+#[test]
+fn fen_lenient_fields() {
+    let full = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    let short = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -").unwrap();
+    assert_eq!(short.to_fen(), full.to_fen());
+    assert_eq!(short.zobrist_hash, full.zobrist_hash);
+
+    let with_moves = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 moves e2e4 e7e5").unwrap();
+    let mut played = Board::new();
+    played.push_uci_line("e2e4 e7e5").unwrap();
+    assert_eq!(with_moves.zobrist_hash, played.zobrist_hash);
+
+    assert!(Board::from_fen_strict("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -").is_err());
+    assert!(Board::from_fen("8/8/8/8/8/8/8/8 w").is_err());
+    assert!(Board::from_fen("8/8/8/8/8/8/8/8 x - -").is_err());
+    assert!(Board::from_fen("8/8/8/8/8/8/8/K6k w - - 5").is_err());
+    assert!(Board::from_fen("8/8/8/8/8/8/8/K6k w - - 0 1 extra").is_err());
+    assert!(Board::from_fen("8/8/8/8/8/8/8/K6k w - - x 1").is_err());
+    assert!(Board::from_fen("8/8/8/8/8/8/8/K6k w - - 0 y").is_err());
+}
+
+#[test]
+fn fen_rejects_malformed_en_passant() {
+    for ep in ["e", "E3", "e4", "i3", "e33", "3e"] {
+        assert!(Board::from_fen(&format!("8/8/8/8/8/8/8/K6k w - {ep}")).is_err(), "{ep}");
+    }
+    assert_eq!(Board::from_fen("8/8/8/8/8/8/8/K6k b - e3").unwrap().en_passant_target, Some(20));
+}
+
+#[test]
+fn gives_checkmate_matches_make_move() {
+    // Mates em 1: corredor, dama apoiada, mate do pastor, promoção e roque; Kiwipete não tem nenhum
+    let fens = [
+        "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1",
+        "7k/8/6K1/8/8/8/8/1Q6 w - - 0 1",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5Q2/PPPP1PPP/RNB1K1NR w KQkq - 0 1",
+        "k7/2P5/1K6/8/8/8/8/8 w - - 0 1",
+        "2rkr3/2p1p3/8/8/8/8/8/R3K3 w Q - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    ];
+    let mut mates = 0;
+    for fen in fens {
+        let board = Board::from_fen(fen).expect("FEN inválido");
+        for mv in board.generate_legal_moves() {
+            let mut after = board;
+            after.make_move(mv);
+            assert_eq!(board.gives_checkmate(mv), after.is_checkmate(), "{} em {}", mv, fen);
+            mates += after.is_checkmate() as u32;
+        }
+    }
+    assert!(mates >= 5, "só {} mates encontrados", mates);
+}
+
+#[test]
+fn find_legal_move_detects_special_moves() {
+    let square = |name: &str| {
+        let bytes = name.as_bytes();
+        (bytes[1] - b'1') * 8 + (bytes[0] - b'a')
+    };
+
+    // Roque: rei duas casas ou rei sobre a torre
+    let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    let castle = board.find_legal_move(square("e1"), square("g1"), None).expect("roque pequeno");
+    assert!(castle.is_castling);
+    let long_castle = board.find_legal_move(square("e1"), square("a1"), None).expect("roque grande");
+    assert_eq!(Some(long_castle), board.find_legal_move(square("e1"), square("c1"), None));
+    assert!(board.find_legal_move(square("e1"), square("e3"), None).is_none());
+
+    // En passant
+    let board = Board::from_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3").unwrap();
+    assert!(board.find_legal_move(square("e5"), square("d6"), None).expect("en passant").is_en_passant);
+
+    // Promoção: dama por omissão, ou a peça pedida
+    let board = Board::from_fen("8/P6k/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let queen = board.find_legal_move(square("a7"), square("a8"), None).unwrap();
+    assert_eq!(queen.promotion, Some(PieceKind::Queen));
+    let knight = board.find_legal_move(square("a7"), square("a8"), Some(PieceKind::Knight)).unwrap();
+    assert_eq!(knight.promotion, Some(PieceKind::Knight));
+}
+
+#[test]
+fn fen_piece_placement_start_position() {
+    let board = Board::new();
+    assert_eq!(board.fen_piece_placement(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR");
+    assert!(board.to_fen().starts_with(&board.fen_piece_placement()));
+}
+
+#[test]
+fn with_move_leaves_board_unchanged() {
+    let board = Board::new();
+    let mv = board.parse_uci_move("e2e4").unwrap();
+    let after = board.with_move(mv);
+
+    assert_eq!(board.to_fen(), Board::new().to_fen());
+    assert_eq!(after.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+    assert_eq!(after.zobrist_hash, after.compute_zobrist_hash());
+    assert_ne!(after.zobrist_hash, board.zobrist_hash);
+}
+
+#[test]
+fn board_builder_kq_vs_k() {
+    let board = BoardBuilder::new()
+        .put(4, Color::White, PieceKind::King)
+        .put(3, Color::White, PieceKind::Queen)
+        .put(60, Color::Black, PieceKind::King)
+        .side_to_move(Color::Black)
+        .build()
+        .expect("posição válida");
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/3QK3 b - - 0 1");
+    assert_eq!(board.zobrist_hash, Board::from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1").unwrap().zobrist_hash);
+
+    let start = BoardBuilder::new().castling(0b1111);
+    let start = [PieceKind::Rook, PieceKind::Knight, PieceKind::Bishop, PieceKind::Queen, PieceKind::King, PieceKind::Bishop, PieceKind::Knight, PieceKind::Rook]
+        .iter()
+        .enumerate()
+        .fold(start, |builder, (file, &kind)| {
+            builder
+                .put(file as u8, Color::White, kind)
+                .put(8 + file as u8, Color::White, PieceKind::Pawn)
+                .put(48 + file as u8, Color::Black, PieceKind::Pawn)
+                .put(56 + file as u8, Color::Black, kind)
+        });
+    assert_eq!(start.build().unwrap().zobrist_hash, Board::new().zobrist_hash);
+}
+
+#[test]
+fn is_legal_move_rejects_malformed_moves() {
+    let board = Board::new();
+    let bogus = |from: u8, to: u8| Move { from, to, promotion: None, is_castling: false, is_en_passant: false };
+
+    assert!(!board.is_legal_move(bogus(28, 36)), "origem vazia");
+    assert!(!board.is_legal_move(bogus(52, 44)), "peça do adversário");
+    assert!(!board.is_legal_move(bogus(0, 8)), "captura de peça própria");
+    assert!(!board.is_legal_move(bogus(64, 8)), "casa fora do tabuleiro");
+    assert!(!board.is_legal_move(Move::null()));
+    assert!(board.is_legal_move(bogus(12, 28)));
+}
+
+#[test]
+fn same_position_ignores_clocks() {
+    let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    let later = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 12 30").unwrap();
+    assert!(board.same_position(&later));
+    assert_ne!(board.halfmove_clock, later.halfmove_clock);
+
+    // Um alvo de en passant que nenhum peão pode usar não distingue posições
+    let dead_ep = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let no_ep = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert!(dead_ep.same_position(&no_ep));
+
+    assert!(!board.same_position(&Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1").unwrap()));
+    assert!(!board.same_position(&Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w Kkq - 0 1").unwrap()));
+
+    // Chess960: a torre de um direito já perdido não distingue posições
+    let mut lost_right = Board::from_fen("7k/8/8/8/8/8/8/1RK5 w B - 0 1").unwrap();
+    lost_right.push_uci_line("b1b2 h8g8 b2b1 g8h8").unwrap();
+    let parsed = Board::from_fen("7k/8/8/8/8/8/8/1RK5 w - - 4 3").unwrap();
+    assert_ne!(lost_right.castling_rooks, parsed.castling_rooks);
+    assert!(lost_right.same_position(&parsed));
+    assert_eq!(lost_right.tt_key(), parsed.tt_key());
+}
+
+#[test]
+fn same_position_agrees_with_tt_key() {
+    fn collect(board: &Board, depth: u8, out: &mut Vec<Board>) {
+        out.push(*board);
+        if depth > 0 {
+            for mv in board.generate_legal_moves() {
+                collect(&board.with_move(mv), depth - 1, out);
+            }
+        }
+    }
+
+    let mut boards = Vec::new();
+    collect(&Board::new(), 3, &mut boards);
+    collect(&Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap(), 2, &mut boards);
+
+    // Posições com a mesma chave são iguais; posições vizinhas com chaves diferentes não o são
+    let mut representatives: HashMap<u64, Board> = HashMap::new();
+    for board in &boards {
+        let representative = representatives.entry(board.tt_key()).or_insert(*board);
+        assert!(representative.same_position(board), "{} vs {}", representative.to_fen(), board.to_fen());
+    }
+    for pair in boards.windows(2) {
+        assert_eq!(pair[0].same_position(&pair[1]), pair[0].tt_key() == pair[1].tt_key());
+    }
+    assert!(representatives.len() < boards.len(), "nenhuma transposição encontrada");
+}
+
+#[test]
+fn chess960_castling_uci_is_king_takes_rook() {
+    // Rei em b1 e torre em a1: o roque grande leva o rei a c1, tal como o lance normal "b1c1"
+    let board = Board::from_fen("1k6/8/8/8/8/8/8/RK6 w A - 0 1").unwrap();
+    let castle = board.parse_uci_move("b1a1").expect("roque legal");
+    assert!(castle.is_castling);
+    assert_eq!(castle.to, 2);
+    assert_eq!(board.move_to_uci(castle), "b1a1");
+
+    let king_move = board.parse_uci_move("b1c1").expect("lance legal");
+    assert!(!king_move.is_castling);
+    assert_eq!(board.move_to_uci(king_move), "b1c1");
+
+    // Ida e volta de todos os lances legais
+    for mv in board.generate_legal_moves() {
+        assert_eq!(board.parse_uci_move(&board.move_to_uci(mv)), Ok(mv));
+    }
+    let after = board.with_move(castle);
+    assert_eq!(after.to_fen(), "1k6/8/8/8/8/8/8/2KR4 b - - 1 1");
+
+    // No xadrez clássico o roque continua a escrever-se com o destino do rei
+    let standard = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let castle = standard.parse_uci_move("e1g1").expect("roque legal");
+    assert!(castle.is_castling);
+    assert_eq!(standard.move_to_uci(castle), "e1g1");
+    assert_eq!(standard.parse_uci_move("e1h1"), Ok(castle));
+}
+
+#[test]
+fn gives_check_matches_make_move() {
+    // Percorre a árvore e compara com o xeque após make_move: descobertos, en passant, roque e promoções
+    fn walk(board: &Board, depth: u8, checks: &mut u32) {
+        if depth == 0 {
+            return;
+        }
+        for mv in board.generate_legal_moves() {
+            let after = board.with_move(mv);
+            let in_check = after.is_king_in_check(after.to_move);
+            assert_eq!(board.gives_check(mv), in_check, "{} em {}", mv, board.to_fen());
+            *checks += in_check as u32;
+            walk(&after, depth - 1, checks);
+        }
+    }
+
+    let fens = [
+        ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 3),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4),
+        ("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 3),
+        ("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", 3),
+        ("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", 3),
+    ];
+    for (fen, depth) in fens {
+        let board = Board::from_fen(fen).expect("FEN inválido");
+        let mut checks = 0;
+        walk(&board, depth, &mut checks);
+        assert!(checks > 0, "nenhum xeque em {}", fen);
+    }
+}
+
+#[test]
+fn check_cache_matches_recomputation_in_random_games() {
+    // Partidas aleatórias (gerador congruencial fixo) alternando make_move e make_move_with_undo;
+    // os flags de xeque, pendentes ou não, têm de coincidir com os de uma posição recalculada
+    fn assert_check_flags(board: &Board) {
+        let fresh = Board::from_fen(&board.to_fen()).expect("FEN inválido");
+        for color in [Color::White, Color::Black] {
+            assert_eq!(board.is_king_in_check(color), fresh.is_king_in_check(color), "{:?} em {}", color, board.to_fen());
+        }
+    }
+
+    let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut next = |bound: usize| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) as usize % bound
+    };
+    for game in 0..40 {
+        let mut board = if game % 2 == 0 {
+            Board::new()
+        } else {
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap()
+        };
+        let mut history = Vec::new();
+        for _ in 0..80 {
+            let moves = board.generate_legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            let mv = moves[next(moves.len())];
+            if next(2) == 0 {
+                history.push((mv, board.make_move_with_undo(mv)));
+            } else {
+                board.make_move(mv);
+                history.clear();
+            }
+            assert_check_flags(&board);
+        }
+        while let Some((mv, undo)) = history.pop() {
+            board.unmake_move(mv, undo);
+            assert_check_flags(&board);
+        }
+    }
+}
+
+/// Joga os lances UCI guardando o `zobrist_hash` de cada posição anterior
+fn play_with_history(board: &mut Board, moves: &str) -> Vec<u64> {
+    let mut history = Vec::new();
+    for token in moves.split_whitespace() {
+        let mv = board.parse_uci_move(token).expect("lance legal");
+        history.push(board.zobrist_hash);
+        board.make_move(mv);
+    }
+    history
+}
+
+#[test]
+fn is_draw_covers_every_draw_pathway() {
+    // Afogamento
+    assert!(Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap().is_draw());
+    // Regra dos 50 lances
+    assert!(Board::from_fen("7k/8/6K1/8/8/8/8/R7 w - - 100 80").unwrap().is_draw());
+    assert!(!Board::from_fen("7k/8/6K1/8/8/8/8/R7 w - - 99 80").unwrap().is_draw());
+    // Material insuficiente
+    assert!(Board::from_fen("7k/8/6K1/8/8/8/8/N7 w - - 0 1").unwrap().is_draw());
+    // Xeque-mate e posição inicial não são empate
+    assert!(!Board::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap().is_draw());
+    assert!(!Board::new().is_draw());
+
+    // Repetição tripla: os cavalos vão e voltam duas vezes
+    let mut board = Board::new();
+    let mut history = play_with_history(&mut board, "g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1");
+    assert!(!board.is_draw_with_history(&history));
+    history.extend(play_with_history(&mut board, "f6g8"));
+    assert!(board.is_draw_by_repetition(&history));
+    assert!(board.is_draw_with_history(&history));
+    // Sem histórico a repetição não é detetável
+    assert!(!board.is_draw());
+
+    // Um lance irreversível pelo meio corta o histórico
+    let mut board = Board::new();
+    let history = play_with_history(&mut board, "g1f3 g8f6 f3g1 f6g8 e2e4 e7e5 g1f3 g8f6 f3g1 f6g8");
+    assert!(!board.is_draw_with_history(&history));
+}
+
+#[test]
+fn outcome_maps_known_positions() {
+    let outcome = |fen: &str| Board::from_fen(fen).expect("FEN inválido").outcome();
+    assert_eq!(Board::new().outcome(), GameResult::Ongoing);
+    // Mate do louco e mate de corredor
+    assert_eq!(
+        outcome("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"),
+        GameResult::BlackWins
+    );
+    assert_eq!(outcome("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1"), GameResult::WhiteWins);
+    assert_eq!(outcome("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), GameResult::Draw(DrawReason::Stalemate));
+    assert_eq!(outcome("7k/8/6K1/8/8/8/8/R7 w - - 100 80"), GameResult::Draw(DrawReason::FiftyMove));
+    assert_eq!(outcome("7k/8/6K1/8/8/8/8/B7 w - - 0 1"), GameResult::Draw(DrawReason::InsufficientMaterial));
+    // O mate prevalece sobre a regra dos 50 lances
+    assert_eq!(outcome("R5k1/5ppp/8/8/8/8/8/6K1 b - - 100 80"), GameResult::WhiteWins);
+
+    // A repetição só aparece com o histórico
+    let mut board = Board::new();
+    let history = play_with_history(&mut board, "b1c3 b8c6 c3b1 c6b8 b1c3 b8c6 c3b1 c6b8");
+    assert_eq!(board.outcome(), GameResult::Ongoing);
+    assert_eq!(board.outcome_with_history(&history), GameResult::Draw(DrawReason::Repetition));
+}
+
+#[test]
+fn evasions_match_legal_moves_in_check() {
+    // Em todas as posições em xeque da árvore, as evasões legais são exatamente os lances legais
+    fn walk(board: &Board, depth: u8, in_check: &mut u32) {
+        if board.is_king_in_check(board.to_move) {
+            *in_check += 1;
+            let mut evasions: Vec<Move> = board.generate_evasions().into_iter().filter(|&mv| board.is_legal_move(mv)).collect();
+            let mut legal = board.generate_legal_moves();
+            let key = |mv: &Move| (mv.from, mv.to, mv.promotion.map(|kind| kind.to_char()));
+            evasions.sort_by_key(key);
+            legal.sort_by_key(key);
+            assert_eq!(evasions, legal, "{}", board.to_fen());
+        }
+        if depth > 0 {
+            for mv in board.generate_legal_moves() {
+                walk(&board.with_move(mv), depth - 1, in_check);
+            }
+        }
+    }
+
+    let mut in_check = 0;
+    for (fen, depth) in [
+        ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 2),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4),
+        ("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 2),
+    ] {
+        walk(&Board::from_fen(fen).expect("FEN inválido"), depth, &mut in_check);
+    }
+    assert!(in_check > 100, "só {} posições em xeque", in_check);
+
+    // Xeque duplo (torre e cavalo): só lances de rei
+    let double_check = Board::from_fen("4r2k/8/8/8/8/3n4/8/R3K3 w Q - 0 1").unwrap();
+    walk(&double_check, 0, &mut in_check);
+    assert!(double_check.generate_evasions().iter().all(|mv| mv.from == 4 && !mv.is_castling));
+}
+
+#[test]
+fn smallest_attacker_walks_up_the_values() {
+    // e5 atacado por Cf3, Bb2, Te2 e, em raio X atrás da torre, De1; defendido por f6
+    let board = Board::from_fen("6k1/8/5p2/4p3/8/5N2/1B2R3/4Q1K1 w - - 0 1").unwrap();
+    let e5 = 36;
+    let mut occupied = board.occupied();
+    let mut sequence = Vec::new();
+    while let Some((square, kind)) = board.smallest_attacker(e5, Color::White, occupied) {
+        sequence.push((square, kind));
+        occupied &= !(1u64 << square);
+    }
+    assert_eq!(
+        sequence,
+        [(21, PieceKind::Knight), (9, PieceKind::Bishop), (12, PieceKind::Rook), (4, PieceKind::Queen)]
+    );
+    assert_eq!(board.smallest_attacker(e5, Color::Black, board.occupied()), Some((45, PieceKind::Pawn)));
+    assert_eq!(board.smallest_attacker(20, Color::Black, board.occupied()), None);
+
+    // Cxe5 fxe5 Bxe5: 100 - 320 + 100 = -120, exatamente no limiar
+    let capture = board.parse_uci_move("f3e5").unwrap();
+    assert!(board.see_ge(capture, -120));
+    assert!(!board.see_ge(capture, -119));
+    assert!(!board.see_ge(capture, 0));
+    // Um lance sem captura para uma casa segura vale 0
+    let quiet = board.parse_uci_move("f3h4").unwrap();
+    assert!(board.see_ge(quiet, 0));
+    assert!(!board.see_ge(quiet, 1));
+}
+
+#[test]
+fn see_ge_exchange_balances() {
+    // (FEN, lance, valor exato da troca)
+    let cases = [
+        // Ganha: peão captura cavalo defendido
+        ("4k3/2p5/3n4/4P3/8/8/8/4K3 w - - 0 1", "e5d6", 220),
+        // Perde: dama captura peão defendido
+        ("4k3/3p4/4p3/8/8/8/8/4QK2 w - - 0 1", "e1e6", -800),
+        // Igual: cavalo por cavalo
+        ("4k3/8/2p5/3n4/8/4N3/8/4K3 w - - 0 1", "e3d5", 0),
+        // Raio X: a segunda torre branca atrás da primeira decide a troca
+        ("3rk3/8/8/3p4/8/8/3R4/3RK3 w - - 0 1", "d2d5", 100),
+        ("3rk3/8/8/3p4/8/8/3R4/4K3 w - - 0 1", "d2d5", -400),
+        // Promoção: a peça promovida não entra no saldo
+        ("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8q", 0),
+        ("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8q", -100),
+        ("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7b8q", 500),
+    ];
+    for (fen, uci, value) in cases {
+        let board = Board::from_fen(fen).expect("FEN inválido");
+        let mv = board.parse_uci_move(uci).expect("lance legal");
+        assert!(board.see_ge(mv, value), "{} em {} deveria valer {}", uci, fen, value);
+        assert!(!board.see_ge(mv, value + 1), "{} em {} deveria valer {}", uci, fen, value);
+    }
+}
+
+#[test]
+fn null_move_restores_the_position() {
+    let mut board = Board::new();
+    let mv = board.parse_uci_move("e2e4").unwrap();
+    let undo = board.make_move_with_undo(mv);
+    let before = (board.to_fen(), board.zobrist_hash, undoable_state(&board));
+
+    let null = board.make_null_move();
+    assert_eq!(board.to_move, Color::White);
+    assert_eq!(board.en_passant_target, None);
+    assert_eq!(board.zobrist_hash, board.compute_zobrist_hash());
+    assert_ne!(board.zobrist_hash, before.1);
+    assert!(!board.is_king_in_check(Color::White) && !board.is_king_in_check(Color::Black));
+
+    board.unmake_null_move(null);
+    assert_eq!((board.to_fen(), board.zobrist_hash, undoable_state(&board)), before);
+    board.unmake_move(mv, undo);
+    assert_eq!(board.to_fen(), Board::new().to_fen());
+}
+
+#[test]
+fn zobrist_after_matches_make_move() {
+    fn walk(board: &Board, depth: u8) {
+        for mv in board.generate_legal_moves() {
+            let after = board.with_move(mv);
+            assert_eq!(board.zobrist_after(mv), after.zobrist_hash, "{} em {}", mv, board.to_fen());
+            if depth > 1 {
+                walk(&after, depth - 1);
+            }
+        }
+    }
+
+    for (fen, depth) in [
+        ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 3),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4),
+        ("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1", 3),
+        ("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", 3),
+    ] {
+        walk(&Board::from_fen(fen).expect("FEN inválido"), depth);
+    }
+}
+
+#[test]
+fn tt_key_ignores_dead_en_passant() {
+    // Depois de 1.e4 nenhum peão preto pode capturar em e3
+    let mut board = Board::new();
+    board.push_uci_line("e2e4").unwrap();
+    let no_ep = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_eq!(board.en_passant_target, Some(20));
+    assert_ne!(board.zobrist_hash, no_ep.zobrist_hash);
+    assert_eq!(board.tt_key(), no_ep.tt_key());
+
+    // Com um peão preto em d4 a captura é possível e a chave distingue as posições
+    let live = Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let live_no_ep = Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_eq!(live.tt_key(), live.zobrist_hash);
+    assert_ne!(live.tt_key(), live_no_ep.tt_key());
+}
+
+#[test]
+fn material_balance_counts_white_minus_black() {
+    assert_eq!(Board::new().material_balance(), 0);
+    // Sem o cavalo de g8 as brancas têm um cavalo a mais
+    let knight_up = Board::from_fen("rnbqkb1r/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(knight_up.material_balance(), PieceKind::Knight.value());
+    assert_eq!(knight_up.material_balance(), 320);
+    // Troca de sinal ao espelhar as cores
+    assert_eq!(knight_up.flip().material_balance(), -320);
+    // Os reis não contam
+    assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/Q3K3 w - - 0 1").unwrap().material_balance(), 900);
+}
+
+#[test]
+fn push_uci_line_scholars_mate() {
+    let mut board = Board::new();
+    board.push_uci_line("e2e4 e7e5 f1c4 b8c6 d1h5 g8f6 h5f7").unwrap();
+    assert!(board.is_checkmate());
+    assert_eq!(board.outcome(), GameResult::WhiteWins);
+    assert_eq!(board.to_fen().split(' ').next(), Some("r1bqkb1r/pppp1Qpp/2n2n2/4p3/2B1P3/8/PPPP1PPP/RNB1K1NR"));
+
+    // O mesmo via sufixo "moves" da FEN
+    let startpos = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    let from_fen = Board::from_fen(&format!("{} moves e2e4 e7e5 f1c4 b8c6 d1h5 g8f6 h5f7", startpos)).unwrap();
+    assert!(from_fen.same_position(&board));
+
+    // Pára no primeiro lance ilegal, mantendo os anteriores
+    let mut board = Board::new();
+    assert_eq!(board.push_uci_line("e2e4 e7e5 e4e5 d7d5"), Err("Illegal move: e4e5".to_string()));
+    assert_eq!(board.to_move, Color::White);
+    assert_eq!(board.mailbox[36].map(|piece| piece.kind), Some(PieceKind::Pawn));
+}
+
+#[test]
+fn san_lines_and_special_moves() {
+    // Abertura espanhola
+    let mut board = Board::new();
+    let applied = board.apply_moves_san("1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 *").unwrap();
+    assert_eq!(applied.len(), 6);
+    assert_eq!(board.to_fen(), "r1bqkbnr/1ppp1ppp/p1n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 0 4");
+
+    // Desambiguação por coluna e por linha
+    let knights = Board::from_fen("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1").unwrap();
+    assert_eq!(knights.parse_san("Nd2"), Err("Ambiguous move: Nd2".to_string()));
+    assert_eq!(knights.parse_san("Nbd2").unwrap().from, 1);
+    assert_eq!(knights.parse_san("Nfd2").unwrap().from, 5);
+    let rooks = Board::from_fen("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1").unwrap();
+    assert_eq!(rooks.parse_san("R1a3").unwrap().from, 0);
+    assert_eq!(rooks.parse_san("R5a3").unwrap().from, 32);
+
+    // Roques
+    let castling = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    assert_eq!(castling.parse_san("O-O").unwrap(), castling.parse_uci_move("e1g1").unwrap());
+    assert_eq!(castling.parse_san("0-0-0").unwrap(), castling.parse_uci_move("e1c1").unwrap());
+
+    // Promoções, com e sem captura
+    let promotion = Board::from_fen("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(promotion.parse_san("e8=Q+").unwrap(), promotion.parse_uci_move("e7e8q").unwrap());
+    assert_eq!(promotion.parse_san("exd8N").unwrap(), promotion.parse_uci_move("e7d8n").unwrap());
+    assert!(promotion.parse_san("e8").is_err());
+}
+
+#[test]
+fn legal_moves_count_and_has_legal_move() {
+    let cases = [
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 20),
+        ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 48),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 14),
+        // Mate e afogamento
+        ("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1", 0),
+        ("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", 0),
+    ];
+    for (fen, count) in cases {
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(board.legal_moves_count(), count, "{}", fen);
+        assert_eq!(board.legal_moves_count(), board.generate_legal_moves().len(), "{}", fen);
+        assert_eq!(board.has_legal_move(), count > 0, "{}", fen);
+    }
+}
+
+#[test]
+fn capture_and_promotion_classification() {
+    // Captura normal, en passant, promoção silenciosa e promoção com captura
+    let board = Board::from_fen("1r2k3/P7/8/3pP3/8/8/5n2/4K3 w - d6 0 1").unwrap();
+    let classify = |uci: &str| {
+        let mv = board.parse_uci_move(uci).expect("lance legal");
+        (board.is_capture(mv), board.is_promotion(mv), board.is_capture_or_promotion(mv))
+    };
+    assert_eq!(classify("e1f2"), (true, false, true));
+    assert_eq!(classify("e5d6"), (true, false, true));
+    assert_eq!(classify("a7a8q"), (false, true, true));
+    assert_eq!(classify("a7b8n"), (true, true, true));
+    assert_eq!(classify("e1e2"), (false, false, false));
+    assert_eq!(classify("e5e6"), (false, false, false));
+    assert!(board.parse_uci_move("e5d6").unwrap().is_en_passant);
+}
+
+#[test]
+fn is_quiet_positions() {
+    let quiet = |fen: &str| Board::from_fen(fen).unwrap().is_quiet();
+    assert!(quiet("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"));
+    // Captura disponível
+    assert!(!quiet("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 1"));
+    // Em xeque
+    assert!(!quiet("4k3/8/8/8/8/8/8/4K2r w - - 0 1"));
+    // Promoção disponível
+    assert!(!quiet("7k/P7/8/8/8/8/8/4K3 w - - 0 1"));
+    // A única captura é de um cavalo cravado: ilegal, por isso a posição é tranquila
+    assert!(quiet("4k3/8/8/8/3pr3/8/4N3/4K3 w - - 0 1"));
+}
+
+#[test]
+fn pinned_pieces_on_lines_and_diagonals() {
+    // Brancas: Ce2 cravado pela torre em e8, Bd2 pelo bispo em b4; Cb1 e Bc1 partilham o raio de a1
+    // Pretas: peão h7 cravado pela torre em h1
+    let board = Board::from_fen("4r2k/7p/8/8/1b6/8/3BN3/rNB1K2R w K - 0 1").unwrap();
+    assert_eq!(board.pinned_pieces(Color::White), (1 << 11) | (1 << 12));
+    assert_eq!(board.pinned_pieces(Color::Black), 1 << 55);
+    assert_eq!(Board::new().pinned_pieces(Color::White), 0);
+
+    // Peças cravadas só se movem sobre o raio da cravação
+    for mv in board.generate_legal_moves() {
+        assert_ne!(mv.from, 12, "o cavalo cravado não se pode mover: {}", mv);
+        if mv.from == 11 {
+            assert!([18, 25].contains(&mv.to), "bispo cravado fora do raio: {}", mv);
+        }
+    }
+}
+
+#[test]
+fn double_check_allows_only_king_moves() {
+    // Torre em e8 e cavalo em d3 dão xeque ao rei em e1
+    let board = Board::from_fen("4r2k/8/8/8/8/3n4/2P5/R1B1K3 w Q - 0 1").unwrap();
+    assert_eq!(board.checkers(Color::White), (1 << 60) | (1 << 19));
+    let moves = board.generate_legal_moves();
+    assert!(!moves.is_empty());
+    assert!(moves.iter().all(|mv| mv.from == 4 && !mv.is_castling), "{:?}", moves);
+    // Capturar só um dos atacantes não chega
+    assert!(board.parse_uci_move("c2d3").is_err());
+
+    // Xeque simples: capturar o atacante é permitido
+    let single = Board::from_fen("7k/8/8/8/8/3n4/2P5/4K3 w - - 0 1").unwrap();
+    assert_eq!(single.checkers(Color::White).count_ones(), 1);
+    assert!(single.parse_uci_move("c2d3").is_ok());
+}
+
+#[test]
+fn attackers_to_crowded_square() {
+    // d4 atacado por peões, cavalo, bispo, torre, dama e rei brancos; pelas pretas há uma torre atrás da dama
+    let board = Board::from_fen("3r3k/3q4/1B6/1n2p3/Q3K3/2P1PN2/8/3R4 w - - 0 1").unwrap();
+    let d4 = 27;
+    let occupied = board.occupied();
+    let squares = |bb: u64| (0..64).filter(|sq| bb & (1u64 << sq) != 0).collect::<Vec<u8>>();
+    assert_eq!(squares(board.attackers_to(d4, Color::White, occupied)), [3, 18, 20, 21, 24, 28, 41]);
+    assert_eq!(squares(board.attackers_to(d4, Color::Black, occupied)), [33, 36, 51]);
+
+    // Tirando a dama da ocupação aparece a torre em raio X
+    let without_queen = occupied & !(1u64 << 51);
+    assert_eq!(squares(board.attackers_to(d4, Color::Black, without_queen) & without_queen), [33, 36, 59]);
+    // Os atacantes coincidem com is_square_attacked_by em todas as casas
+    for square in 0..64 {
+        for color in [Color::White, Color::Black] {
+            assert_eq!(board.attackers_to(square, color, occupied) != 0, board.is_square_attacked_by(square, color), "{}", square);
+        }
+    }
+}
+
+#[test]
+fn display_snapshot() {
+    let mut board = Board::new();
+    board.push_uci_line("e2e4").unwrap();
+    let expected = "\
+8 r n b q k b n r
+7 p p p p p p p p
+6 . . . . . . . .
+5 . . . . . . . .
+4 . . . . P . . .
+3 . . . . . . . .
+2 P P P P . P P P
+1 R N B Q K B N R
+  a b c d e f g h
+Vez: Pretas | Roque: KQkq | En passant: e3";
+    assert_eq!(board.to_string(), expected);
+}
+
+#[test]
+fn fen_keeps_the_fullmove_number() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 3 17";
+    let mut board = Board::from_fen(fen).unwrap();
+    assert_eq!(board.fullmove_number, 17);
+    assert_eq!(board.to_fen(), fen);
+
+    // Sobe depois do lance das pretas e volta atrás com unmake_move
+    let mv = board.parse_uci_move("e7d8").unwrap();
+    let undo = board.make_move_with_undo(mv);
+    assert_eq!(board.fullmove_number, 18);
+    board.unmake_move(mv, undo);
+    assert_eq!(board.to_fen(), fen);
+
+    // Sem o campo (ou com 0) fica 1
+    assert_eq!(Board::from_fen("8/8/8/8/8/8/8/K6k w - -").unwrap().fullmove_number, 1);
+    assert_eq!(Board::from_fen("8/8/8/8/8/8/8/K6k w - - 5 0").unwrap().fullmove_number, 1);
+    assert_eq!(BoardBuilder::new().fullmove_number(40).fen(), "8/8/8/8/8/8/8/8 w - - 0 40");
+}
+
+#[test]
+fn fen_chars_round_trip() {
+    // As 12 combinações de peça e cor
+    for color in [Color::White, Color::Black] {
+        for kind in PieceKind::ALL {
+            let ch = fen_char(kind, color);
+            assert_eq!(ch.is_ascii_uppercase(), color == Color::White, "{}", ch);
+            assert_eq!(PieceKind::from_char(ch), Some(kind));
+            assert_eq!(kind.to_char(), ch.to_ascii_lowercase());
+        }
+    }
+    assert_eq!(
+        PieceKind::ALL.map(|kind| fen_char(kind, Color::White)).iter().collect::<String>(),
+        "PNBRQK"
+    );
+    assert_eq!(PieceKind::from_char('x'), None);
+}
+
+#[test]
+fn color_not_and_opponent() {
+    assert_eq!(!Color::White, Color::Black);
+    assert_eq!(!Color::Black, Color::White);
+    for color in [Color::White, Color::Black] {
+        assert_eq!(color.opponent(), !color);
+        assert_eq!(!!color, color);
+    }
+    // O lado a jogar alterna a cada lance
+    let board = Board::new();
+    assert_eq!(board.with_move(board.parse_uci_move("e2e4").unwrap()).to_move, !board.to_move);
+}
+
+#[test]
+fn piece_kinds_and_values_are_ordered() {
+    use pelanca::{color_to_index, piece_to_index};
+
+    // ALL e BOTH seguem os índices Zobrist
+    for (index, &kind) in PieceKind::ALL.iter().enumerate() {
+        assert_eq!(piece_to_index(kind), index);
+    }
+    for (index, &color) in Color::BOTH.iter().enumerate() {
+        assert_eq!(color_to_index(color), index);
+    }
+
+    // Valores em centipeões, estritamente crescentes de peão a rei
+    assert_eq!(
+        PieceKind::ALL.map(|kind| kind.value()),
+        [100, 320, 330, 500, 900, 20000]
+    );
+    assert!(PieceKind::ALL.windows(2).all(|pair| pair[0].value() < pair[1].value()));
+
+    // Ordenação MVV-LVA com value(): vítima mais valiosa primeiro, depois o atacante mais barato
+    let board = Board::from_fen("n3k3/8/2q1r3/3P4/1N6/8/7K/Q7 w - - 0 1").unwrap();
+    let mut captures: Vec<Move> = board.generate_legal_moves().into_iter().filter(|&mv| board.is_capture(mv)).collect();
+    let kind_at = |square: u8| board.mailbox[square as usize].unwrap().kind;
+    captures.sort_by_key(|mv| (-kind_at(mv.to).value(), kind_at(mv.from).value()));
+    let ordered: Vec<String> = captures.iter().map(|mv| mv.to_string()).collect();
+    assert_eq!(ordered, ["d5c6", "b4c6", "d5e6", "a1a8"]);
+}
+
+#[test]
+fn tapered_material_blends_midgame_and_endgame_values() {
+    use pelanca::MAX_PHASE;
+
+    // Extremos e ponto médio da interpolação
+    for kind in PieceKind::ALL {
+        assert_eq!(kind.tapered_value(MAX_PHASE), kind.value_mg());
+        assert_eq!(kind.tapered_value(0), kind.value_eg());
+        assert_eq!(kind.tapered_value(MAX_PHASE / 2), (kind.value_mg() + kind.value_eg()) / 2);
+        assert_eq!(kind.value(), kind.value_mg());
+    }
+    assert_eq!(PieceKind::Rook.tapered_value(6), (500 * 6 + 540 * 18) / 24);
+
+    // Posição inicial: fase máxima e valores de meio-jogo
+    assert_eq!(Board::new().game_phase(), MAX_PHASE);
+
+    // Final de peões puro: fase 0, o peão a mais vale value_eg
+    let pawn_ending = Board::from_fen("4k3/8/8/8/8/8/PP6/4K3 w - - 0 1").unwrap();
+    assert_eq!(pawn_ending.game_phase(), 0);
+    assert_eq!(pawn_ending.tapered_material_balance(), 2 * PieceKind::Pawn.value_eg());
+
+    // Meio caminho: duas torres e duas damas dão fase 12
+    let middle = Board::from_fen("3qk2r/8/8/8/8/8/P7/3QK2R w - - 0 1").unwrap();
+    assert_eq!(middle.game_phase(), 12);
+    assert_eq!(middle.tapered_material_balance(), PieceKind::Pawn.tapered_value(12));
+    assert_eq!(PieceKind::Pawn.tapered_value(12), 110);
+}
@@ -0,0 +1,129 @@
+// Testes do conhecimento de finais: bitbase KPK e termos de avaliação de finais.
+
+use pelanca::engine::*;
+use pelanca::{Board, PieceKind};
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen).expect("FEN inválido")
+}
+
+#[test]
+fn kpk_known_results() {
+    // Rei na 6ª à frente do peão: ganha seja quem for a jogar
+    assert!(probe_kpk(&board("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1")).unwrap() >= KPK_WIN_SCORE);
+    assert!(probe_kpk(&board("4k3/8/4K3/4P3/8/8/8/8 b - - 0 1")).unwrap() <= -KPK_WIN_SCORE);
+
+    // Rei na 5ª: decide a oposição (ganha só se as pretas jogarem)
+    assert_eq!(probe_kpk(&board("8/4k3/8/4K3/4P3/8/8/8 w - - 0 1")), Some(0));
+    assert!(probe_kpk(&board("8/4k3/8/4K3/4P3/8/8/8 b - - 0 1")).unwrap() <= -KPK_WIN_SCORE);
+
+    // Com as cores trocadas
+    assert!(probe_kpk(&board("8/8/8/8/4p3/4k3/8/4K3 w - - 0 1")).unwrap() <= -KPK_WIN_SCORE);
+    assert!(probe_kpk(&board("8/8/8/8/4p3/4k3/8/4K3 b - - 0 1")).unwrap() >= KPK_WIN_SCORE);
+
+    // Peão de torre com o rei defensor no canto: empate
+    assert_eq!(probe_kpk(&board("k7/8/K7/P7/8/8/8/8 w - - 0 1")), Some(0));
+    // Peão que promove sem que o rei o apanhe
+    assert!(probe_kpk(&board("8/P7/8/8/8/8/8/K6k w - - 0 1")).unwrap() >= KPK_WIN_SCORE);
+}
+
+#[test]
+fn kpk_rejects_other_material_and_impossible_pawns() {
+    assert_eq!(probe_kpk(&Board::new()), None);
+    assert_eq!(probe_kpk(&board("4k3/8/8/8/8/8/8/3QK3 w - - 0 1")), None);
+    // Peões na 1ª ou 8ª fila: a FEN é aceite, mas a bitbase não os cobre
+    assert_eq!(probe_kpk(&board("8/8/8/8/8/8/8/P3K2k w - - 0 1")), None);
+    assert_eq!(probe_kpk(&board("P3K2k/8/8/8/8/8/8/8 w - - 0 1")), None);
+    assert_eq!(probe_kpk(&board("p3k2K/8/8/8/8/8/8/8 b - - 0 1")), None);
+}
+
+#[test]
+fn pawn_race_outside_passed_pawn() {
+    // Peão passado de fora na coluna a: o rei preto em g7 está fora do quadrado
+    assert_eq!(pawn_race(&board("8/6k1/6p1/P7/8/6P1/6K1/8 w - - 0 1")), UNSTOPPABLE_PASSER_BONUS);
+    assert_eq!(pawn_race(&board("8/6k1/6p1/P7/8/6P1/6K1/8 b - - 0 1")), -UNSTOPPABLE_PASSER_BONUS);
+    // Com o rei preto em c7 o peão é apanhado
+    assert_eq!(pawn_race(&board("8/2k5/6p1/P7/8/6P1/6K1/8 w - - 0 1")), 0);
+    // Ambos promovem ao mesmo tempo: corrida equilibrada
+    assert_eq!(pawn_race(&board("k7/p7/8/8/8/8/6P1/7K w - - 0 1")), 0);
+    // Só conta em finais de reis e peões
+    assert_eq!(pawn_race(&board("8/6k1/6p1/P7/8/6P1/6K1/7R w - - 0 1")), 0);
+    // Peões na 8ª fila (aceites pela FEN) são ignorados em vez de causarem overflow
+    assert_eq!(pawn_race(&board("P7/6k1/8/8/8/8/6K1/8 w - - 0 1")), 0);
+    assert_eq!(pawn_race(&board("8/6k1/8/8/8/8/6K1/p7 b - - 0 1")), 0);
+}
+
+#[test]
+fn opposition_in_front_of_the_pawn() {
+    // Quem joga perde a oposição, seja o atacante ou o defensor
+    assert_eq!(opposition(&board("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1")), -OPPOSITION_BONUS);
+    assert_eq!(opposition(&board("4k3/8/4K3/4P3/8/8/8/8 b - - 0 1")), -OPPOSITION_BONUS);
+    assert_eq!(opposition(&board("8/4k3/8/4K3/4P3/8/8/8 w - - 0 1")), -OPPOSITION_BONUS);
+    assert_eq!(opposition(&board("8/8/8/4p3/4k3/8/4K3/8 w - - 0 1")), -OPPOSITION_BONUS);
+}
+
+#[test]
+fn opposition_ignores_pawns_behind_the_wrong_king() {
+    // Peão branco à frente dos dois reis
+    assert_eq!(opposition(&board("8/3P4/8/8/8/4k3/8/4K3 w - - 0 1")), 0);
+    // Rei branco atrás do seu peão
+    assert_eq!(opposition(&board("4k3/8/8/8/4P3/8/4K3/8 w - - 0 1")), 0);
+    // Rei adversário entre o rei e o seu peão
+    assert_eq!(opposition(&board("8/8/4K3/8/4k3/4P3/8/8 w - - 0 1")), 0);
+    // Peão longe da coluna dos reis
+    assert_eq!(opposition(&board("4k3/8/4K3/P7/8/8/8/8 w - - 0 1")), 0);
+    // Com outras peças o termo não se aplica
+    assert_eq!(opposition(&board("4k3/8/4K3/4P3/8/8/8/7N w - - 0 1")), 0);
+}
+
+#[test]
+fn mop_up_drives_the_weak_king_to_a_corner() {
+    // KD vs R: o rei preto é empurrado de d5 até h8 com o rei branco sempre a duas casas
+    let path = [
+        "8/8/8/3k4/8/Q2K4/8/8 w - - 0 1",
+        "8/8/4k3/8/4K3/Q7/8/8 w - - 0 1",
+        "8/5k2/8/5K2/8/Q7/8/8 w - - 0 1",
+        "7k/8/5K2/8/8/Q7/8/8 w - - 0 1",
+    ];
+    let scores: Vec<i32> = path.iter().map(|fen| mop_up(&board(fen))).collect();
+    assert!(scores.windows(2).all(|pair| pair[0] < pair[1]), "{scores:?}");
+
+    // Aproximar o rei forte também melhora a pontuação
+    assert!(mop_up(&board("7k/8/5K2/8/8/Q7/8/8 w - - 0 1")) > mop_up(&board("7k/8/8/8/8/Q1K5/8/8 w - - 0 1")));
+    // Na perspectiva do lado fraco a pontuação é simétrica
+    assert_eq!(
+        mop_up(&board("7k/8/5K2/8/8/Q7/8/8 b - - 0 1")),
+        -mop_up(&board("7k/8/5K2/8/8/Q7/8/8 w - - 0 1"))
+    );
+}
+
+#[test]
+fn mop_up_needs_a_decisive_pawnless_lead() {
+    // Com peões no tabuleiro o termo não se aplica
+    assert_eq!(mop_up(&board("7k/8/5K2/8/8/Q7/P7/8 w - - 0 1")), 0);
+    // Um cavalo a mais não chega a MOP_UP_MIN_ADVANTAGE
+    assert_eq!(mop_up(&board("7k/8/5K2/8/8/N7/8/8 w - - 0 1")), 0);
+}
+
+#[test]
+fn opposite_coloured_bishops_are_scaled_towards_a_draw() {
+    // Bispos de cores opostas com um peão a mais: a avaliação é reduzida a metade
+    let ocb = board("4k3/8/8/4b3/8/2PB4/1P6/4K3 w - - 0 1");
+    assert_eq!(drawish_scale_factor(&ocb), SCALE_DRAWISH);
+    let raw = ocb.material_balance();
+    assert_eq!(raw, PieceKind::Pawn.value() * 2);
+    assert_eq!(scale_drawish(&ocb, raw), raw / 2);
+    assert_eq!(scale_drawish(&ocb, -raw), -raw / 2);
+
+    // Bispos da mesma cor não são escalados
+    let same_colour = board("4k3/8/8/3b4/8/2PB4/1P6/4K3 w - - 0 1");
+    assert_eq!(drawish_scale_factor(&same_colour), SCALE_NORMAL);
+    assert_eq!(scale_drawish(&same_colour, 200), 200);
+}
+
+#[test]
+fn rook_endings_on_one_flank_are_scaled_towards_a_draw() {
+    assert_eq!(drawish_scale_factor(&board("6k1/5pp1/8/8/8/8/r4PPP/3R2K1 w - - 0 1")), SCALE_DRAWISH);
+    // Peões nos dois flancos: sem escala
+    assert_eq!(drawish_scale_factor(&board("6k1/p4pp1/8/8/8/8/r4PPP/3R2K1 w - - 0 1")), SCALE_NORMAL);
+}
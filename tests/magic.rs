@@ -0,0 +1,43 @@
+// Binário de teste próprio: nada inicializou as tabelas mágicas antes deste teste.
+
+use pelanca::moves::magic_bitboards::{get_bishop_attacks_magic, get_rook_attacks_magic};
+
+/// Ataques deslizantes calculados casa a casa, sem tabelas
+fn slow_attacks(square: u8, occupancy: u64, directions: &[(i8, i8)]) -> u64 {
+    let mut attacks = 0;
+    for &(file_step, rank_step) in directions {
+        let (mut file, mut rank) = ((square % 8) as i8, (square / 8) as i8);
+        loop {
+            file += file_step;
+            rank += rank_step;
+            if !(0..8).contains(&file) || !(0..8).contains(&rank) {
+                break;
+            }
+            let bb = 1u64 << (rank * 8 + file);
+            attacks |= bb;
+            if occupancy & bb != 0 {
+                break;
+            }
+        }
+    }
+    attacks
+}
+
+#[test]
+fn magic_attacks_initialise_on_first_lookup() {
+    const ROOK: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+    const BISHOP: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+    // Sem chamar init_magic_bitboards nem criar um Board
+    let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+    for _ in 0..200 {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        let occupancy = seed & seed.rotate_left(23);
+        for square in 0..64 {
+            assert_eq!(get_rook_attacks_magic(square, occupancy), slow_attacks(square, occupancy, &ROOK));
+            assert_eq!(get_bishop_attacks_magic(square, occupancy), slow_attacks(square, occupancy, &BISHOP));
+        }
+    }
+}
@@ -0,0 +1,158 @@
+// Testes das tabelas de ataques e das funções do módulo `moves`.
+
+use pelanca::{Board, Color, Move, MoveBuffer, MoveList, PackedMove, MAX_MOVES};
+
+#[test]
+fn knight_and_king_attack_tables() {
+    use pelanca::moves::{king::get_king_attacks, knight::get_knight_attacks};
+
+    // Canto a1: cavalo em b3/c2, rei em a2/b1/b2
+    assert_eq!(get_knight_attacks(0), (1 << 17) | (1 << 10));
+    assert_eq!(get_king_attacks(0), (1 << 1) | (1 << 8) | (1 << 9));
+    // Canto h8
+    assert_eq!(get_knight_attacks(63), (1 << 46) | (1 << 53));
+    assert_eq!(get_king_attacks(63), (1 << 62) | (1 << 55) | (1 << 54));
+    // Bordas (a4, e1) e centro (d4)
+    assert_eq!(get_knight_attacks(24).count_ones(), 4);
+    assert_eq!(get_king_attacks(4).count_ones(), 5);
+    assert_eq!(get_knight_attacks(27).count_ones(), 8);
+    assert_eq!(get_king_attacks(27).count_ones(), 8);
+
+    // Relação simétrica: se A ataca B, B ataca A
+    for a in 0..64u8 {
+        for b in 0..64u8 {
+            assert_eq!(get_knight_attacks(a) >> b & 1, get_knight_attacks(b) >> a & 1);
+            assert_eq!(get_king_attacks(a) >> b & 1, get_king_attacks(b) >> a & 1);
+        }
+    }
+}
+
+#[test]
+fn magic_attacks_match_known_bitboards() {
+    use pelanca::moves::magic_bitboards::{get_bishop_attacks_magic, get_rook_attacks_magic};
+
+    // a1 com o tabuleiro vazio: coluna a e fila 1 inteiras, diagonal a1-h8
+    assert_eq!(get_rook_attacks_magic(0, 0), 0x0101_0101_0101_01FE);
+    assert_eq!(get_bishop_attacks_magic(0, 0), 0x8040_2010_0804_0200);
+
+    // d4 com bloqueadores: a torre para em d6, b4, g4 e d2; o bispo em f6, b6, b2 e e3
+    assert_eq!(get_rook_attacks_magic(27, 0x0000_0800_4200_0800), 0x0000_0808_7608_0800);
+    assert_eq!(get_bishop_attacks_magic(27, 0x0000_2200_0010_0200), 0x0000_2214_0014_0200);
+
+    // h8 vazio e com g8, h7 e g7 ocupadas
+    assert_eq!(get_rook_attacks_magic(63, 0), 0x7F80_8080_8080_8080);
+    assert_eq!(get_bishop_attacks_magic(63, 0), 0x0040_2010_0804_0201);
+    assert_eq!(get_rook_attacks_magic(63, 0x40C0_0000_0000_0000), 0x4080_0000_0000_0000);
+    assert_eq!(get_bishop_attacks_magic(63, 0x40C0_0000_0000_0000), 0x0040_0000_0000_0000);
+}
+
+#[test]
+fn pawn_attackers_reverse_pawn_attacks() {
+    use pelanca::moves::pawn::{get_pawn_attackers, get_pawn_attacks};
+
+    for color in Color::BOTH {
+        for target in 0..64u8 {
+            for from in 0..64u8 {
+                let attacks = get_pawn_attacks(from, color) & (1u64 << target) != 0;
+                let attacker = get_pawn_attackers(target, color) & (1u64 << from) != 0;
+                assert_eq!(attacks, attacker, "{:?} {} -> {}", color, from, target);
+            }
+        }
+    }
+}
+
+#[test]
+fn all_attacks_matches_is_square_attacked_by() {
+    use pelanca::moves::attacks::all_attacks;
+
+    fn walk(board: &Board, depth: u8) {
+        for color in Color::BOTH {
+            let attacks = all_attacks(board, color);
+            for square in 0..64u8 {
+                assert_eq!(
+                    attacks & (1u64 << square) != 0,
+                    board.is_square_attacked_by(square, color),
+                    "{:?} casa {}\n{}", color, square, board
+                );
+            }
+        }
+        if depth > 0 {
+            for mv in board.generate_legal_moves() {
+                walk(&board.with_move(mv), depth - 1);
+            }
+        }
+    }
+
+    walk(&Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap(), 2);
+    walk(&Board::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1").unwrap(), 3);
+}
+
+#[test]
+fn move_list_keeps_insertion_order() {
+    let mut list = MoveList::new();
+    assert!(list.is_empty());
+    let board = Board::new();
+    let moves: Vec<Move> = ["g1f3", "e2e4", "b1c3"].iter().map(|uci| board.parse_uci_move(uci).unwrap()).collect();
+    for &mv in &moves {
+        list.push(mv);
+    }
+    assert_eq!(&list[..], &moves[..]);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), moves);
+    list.clear();
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn move_list_matches_vec_generation() {
+    // Posição com o máximo conhecido de 218 lances legais
+    let fens = [
+        "R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    ];
+    for fen in fens {
+        let board = Board::from_fen(fen).unwrap();
+        let mut list = MoveList::new();
+        board.generate_all_moves_into(&mut list);
+        let mut vec = Vec::new();
+        board.generate_all_moves_into(&mut vec);
+        assert_eq!(&list[..], &vec[..], "{}", fen);
+        assert_eq!(vec, board.generate_all_moves(), "{}", fen);
+        assert!(list.len() <= MAX_MOVES);
+    }
+
+    let board = Board::from_fen(fens[0]).unwrap();
+    assert_eq!(board.legal_moves_count(), 218);
+    let mut list = MoveList::new();
+    board.generate_all_moves_into(&mut list);
+    assert!(list.len() >= 218);
+}
+
+#[test]
+fn packed_move_round_trip() {
+    // Roques, en passant e as quatro promoções (também com captura)
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/Pp2P3/2N2Q1p/1PPBBPPP/R3K2R b KQkq a3 0 1",
+        "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+    ];
+    let mut seen = (false, false, 0);
+    for fen in fens {
+        let board = Board::from_fen(fen).unwrap();
+        for mv in board.generate_legal_moves() {
+            let packed = PackedMove::from(mv);
+            assert_eq!((packed.from_square(), packed.to_square()), (mv.from, mv.to));
+            assert_eq!(Move::try_from(packed), Ok(mv), "{} em {}", mv, fen);
+            seen.0 |= mv.is_castling;
+            seen.1 |= mv.is_en_passant;
+            seen.2 += mv.promotion.is_some() as u32;
+        }
+    }
+    assert!(seen.0 && seen.1 && seen.2 >= 8, "{:?}", seen);
+
+    // O lance nulo também sobrevive; flags desconhecidos são rejeitados
+    assert_eq!(Move::try_from(PackedMove::from(Move::null())), Ok(Move::null()));
+    assert!(Move::try_from(PackedMove(0b0100 << 12)).is_err());
+    assert!(Move::try_from(PackedMove(0b1100 << 12)).is_err());
+}
@@ -0,0 +1,137 @@
+// Testes de correção da geração de lances: perft nas seis posições padrão da CPW
+// (https://www.chessprogramming.org/Perft_Results), com profundidade limitada para CI.
+
+use pelanca::engine::{perft, perft_divide};
+use pelanca::Board;
+
+fn assert_perft(fen: &str, depth: u8, expected: u64) {
+    let mut board = Board::from_fen(fen).expect("FEN inválido");
+    let nodes = perft(&mut board, depth);
+
+    if nodes != expected {
+        let mut divide = perft_divide(&mut board, depth);
+        divide.sort_by_key(|(mv, _)| mv.to_string());
+        let breakdown: Vec<String> = divide.iter().map(|(mv, n)| format!("{}: {}", mv, n)).collect();
+        panic!(
+            "perft({}) = {}, esperado {}\nFEN: {}\n{}",
+            depth, nodes, expected, fen, breakdown.join("\n")
+        );
+    }
+}
+
+#[test]
+fn perft_initial_position() {
+    let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    assert_perft(fen, 1, 20);
+    assert_perft(fen, 2, 400);
+    assert_perft(fen, 3, 8_902);
+    assert_perft(fen, 4, 197_281);
+}
+
+#[test]
+fn perft_kiwipete() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    assert_perft(fen, 1, 48);
+    assert_perft(fen, 2, 2_039);
+    assert_perft(fen, 3, 97_862);
+}
+
+#[test]
+fn perft_position_3() {
+    let fen = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+    assert_perft(fen, 1, 14);
+    assert_perft(fen, 2, 191);
+    assert_perft(fen, 3, 2_812);
+    assert_perft(fen, 4, 43_238);
+    assert_perft(fen, 5, 674_624);
+}
+
+#[test]
+fn perft_position_4() {
+    let fen = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+    assert_perft(fen, 1, 6);
+    assert_perft(fen, 2, 264);
+    assert_perft(fen, 3, 9_467);
+    assert_perft(fen, 4, 422_333);
+}
+
+#[test]
+fn perft_position_4_mirrored() {
+    let fen = "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1";
+    assert_perft(fen, 1, 6);
+    assert_perft(fen, 2, 264);
+    assert_perft(fen, 3, 9_467);
+}
+
+#[test]
+fn perft_position_5() {
+    let fen = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+    assert_perft(fen, 1, 44);
+    assert_perft(fen, 2, 1_486);
+    assert_perft(fen, 3, 62_379);
+}
+
+#[test]
+fn perft_position_6() {
+    let fen = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+    assert_perft(fen, 1, 46);
+    assert_perft(fen, 2, 2_079);
+    assert_perft(fen, 3, 89_890);
+}
+
+#[test]
+fn perft_flip_symmetry() {
+    // Espelhar e trocar as cores não pode alterar o número de nós
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ];
+    for fen in fens {
+        let mut board = Board::from_fen(fen).expect("FEN inválido");
+        let mut flipped = board.flip();
+        assert_eq!(flipped.flip().zobrist_hash, board.zobrist_hash, "FEN: {}", fen);
+        assert_eq!(perft(&mut flipped, 3), perft(&mut board, 3), "FEN: {}", fen);
+    }
+}
+
+// Posições Chess960 com direitos de roque em notação Shredder-FEN (letras de coluna)
+#[test]
+fn perft_chess960() {
+    let fen = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+    assert_perft(fen, 1, 21);
+    assert_perft(fen, 2, 528);
+    assert_perft(fen, 3, 12_189);
+    assert_perft(fen, 4, 326_672);
+
+    let fen = "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9";
+    assert_perft(fen, 1, 21);
+    assert_perft(fen, 2, 807);
+    assert_perft(fen, 3, 18_002);
+
+    let fen = "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9";
+    assert_perft(fen, 1, 20);
+    assert_perft(fen, 2, 479);
+    assert_perft(fen, 3, 10_471);
+}
+
+#[test]
+fn en_passant_horizontal_pin() {
+    // Capturar en passant tira os dois peões da 5ª/4ª fila e expõe o rei à torre ou dama
+    let cases = [
+        ("8/8/8/K2pP2r/8/8/8/7k w - d6 0 1", "e5d6", 6),
+        ("8/8/8/KPp4r/8/8/8/7k w - c6 0 1", "b5c6", 4),
+        ("8/8/8/8/k2Pp2Q/8/8/3K4 b - d3 0 1", "e4d3", 6),
+    ];
+    for (fen, ep, legal_moves) in cases {
+        let board = Board::from_fen(fen).expect("FEN inválido");
+        let mv = board
+            .generate_all_moves()
+            .into_iter()
+            .find(|mv| mv.is_en_passant && mv.to_string() == ep)
+            .expect("en passant pseudo-legal");
+        assert!(!board.is_legal_move(mv), "{} em {}", ep, fen);
+        assert!(board.parse_uci_move(ep).is_err());
+        assert_perft(fen, 1, legal_moves);
+    }
+}
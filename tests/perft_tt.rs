@@ -0,0 +1,28 @@
+// Testes da tabela de transposição do perft.
+
+use pelanca::engine::PerftTT;
+
+#[test]
+fn hashfull_permille_tracks_the_configured_capacity() {
+    let mut tt = PerftTT::with_capacity(1000);
+    assert_eq!(tt.hashfull_permille(), 0);
+
+    for key in 0..250 {
+        tt.insert(key, 1, key);
+    }
+    assert_eq!(tt.entries_used(), 250);
+    assert_eq!(tt.hashfull_permille(), 250);
+
+    // A mesma chave noutra profundidade é outra entrada; repetir a inserção não conta
+    tt.insert(0, 2, 7);
+    tt.insert(0, 2, 7);
+    assert_eq!(tt.entries_used(), 251);
+    assert_eq!(tt.get(0, 2), Some(7));
+
+    // Acima da capacidade satura em 1000
+    for key in 250..3000 {
+        tt.insert(key, 1, key);
+    }
+    assert_eq!(tt.hashfull_permille(), 1000);
+    assert_eq!(PerftTT::new().hashfull_permille(), 0);
+}
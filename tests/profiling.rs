@@ -0,0 +1,33 @@
+// Testes do profiler; só compilam com `--features profiling`.
+#![cfg(feature = "profiling")]
+
+use pelanca::engine::perft_divide;
+use pelanca::profiling::get_profiler;
+use pelanca::Board;
+
+#[test]
+fn report_lists_the_instrumented_hot_paths() {
+    let mut board = Board::new();
+    let nodes: u64 = perft_divide(&mut board, 3).iter().map(|&(_, nodes)| nodes).sum();
+    assert_eq!(nodes, 8902);
+    assert!(get_profiler().counter_value("perft_nodes") >= nodes);
+
+    let report = get_profiler().generate_report();
+    for timer in ["perft_divide", "generate_all_moves"] {
+        let line = report
+            .lines()
+            .find(|line| line.starts_with(&format!("- {}:", timer)))
+            .unwrap_or_else(|| panic!("sem timer {} em\n{}", timer, report));
+        assert!(!line.contains(" 0 chamadas"), "{}", line);
+    }
+    assert!(report.contains("- perft_nodes: "), "{}", report);
+}
+
+#[test]
+fn count_macro_updates_the_global_profiler() {
+    pelanca::count!("test_count_macro");
+    assert_eq!(get_profiler().counter_value("test_count_macro"), 1);
+    pelanca::count!("test_count_macro", 41);
+    assert_eq!(get_profiler().counter_value("test_count_macro"), 42);
+    assert_eq!(get_profiler().counter_value("never_counted"), 0);
+}